@@ -1,7 +1,7 @@
 import { useState } from 'react';
 import { useContract } from '../hooks/useContract';
 import { useWallet } from '../hooks/useWallet';
-import { Roles } from '../utils/constants';
+import { Roles, Currency } from '../utils/constants';
 
 export function GovernancePanel() {
   const { requestRole, requestSpending, executeProposal, isLoading, error } = useContract();
@@ -43,6 +43,8 @@ export function GovernancePanel() {
       await requestSpending(
         spendingRequest.beneficiary,
         amount,
+        Currency.Token,
+        null,
         spendingRequest.description
       );
       alert('Spending proposal submitted successfully!');
@@ -1,7 +1,7 @@
 import { useState } from 'react';
 import { useContract } from '../hooks/useContract';
 import { useWallet } from '../hooks/useWallet';
-import { SubscriptionType, SUBSCRIPTION_AMOUNTS } from '../utils/constants';
+import { SubscriptionType, BillingPeriod, Currency, SUBSCRIPTION_AMOUNTS } from '../utils/constants';
 
 export function SubscriptionCard() {
   const { createSubscription, updateSubscription, isLoading, error } = useContract();
@@ -10,6 +10,8 @@ export function SubscriptionCard() {
   const [formData, setFormData] = useState({
     name: '',
     subscriptionType: SubscriptionType.Free,
+    period: BillingPeriod.Monthly as BillingPeriod,
+    currency: Currency.Token as Currency,
     institutional: false,
     school: false
   });
@@ -23,6 +25,8 @@ export function SubscriptionCard() {
       await createSubscription(
         formData.name,
         formData.subscriptionType,
+        formData.period,
+        formData.currency,
         formData.institutional,
         formData.school
       );
@@ -31,6 +35,8 @@ export function SubscriptionCard() {
       setFormData({
         name: '',
         subscriptionType: SubscriptionType.Free,
+        period: BillingPeriod.Monthly,
+        currency: Currency.Token,
         institutional: false,
         school: false
       });
@@ -166,6 +172,58 @@ export function SubscriptionCard() {
           </select>
         </div>
 
+        {isNewUser && (
+          <div className="mb-6">
+            <label className="block text-xs tracking-wider uppercase mb-3" style={{ color: 'var(--color-luxury-gray)', letterSpacing: '0.1em' }}>
+              Billing Period
+            </label>
+            <select
+              value={formData.period}
+              onChange={(e) => setFormData({ ...formData, period: e.target.value as any })}
+              className="border-b-2 w-full py-3 px-0 font-light leading-tight focus:outline-none transition-all cursor-pointer"
+              style={{
+                backgroundColor: 'var(--color-luxury-cream)',
+                color: 'var(--color-luxury-black)',
+                borderBottomColor: 'var(--color-luxury-light-gray)'
+              }}
+              onFocus={(e) => e.target.style.borderBottomColor = 'var(--color-luxury-gold)'}
+              onBlur={(e) => e.target.style.borderBottomColor = 'var(--color-luxury-light-gray)'}
+            >
+              {Object.keys(BillingPeriod).map((period) => (
+                <option key={period} value={period}>
+                  {period}
+                </option>
+              ))}
+            </select>
+          </div>
+        )}
+
+        {isNewUser && (
+          <div className="mb-6">
+            <label className="block text-xs tracking-wider uppercase mb-3" style={{ color: 'var(--color-luxury-gray)', letterSpacing: '0.1em' }}>
+              Pay With
+            </label>
+            <select
+              value={formData.currency}
+              onChange={(e) => setFormData({ ...formData, currency: e.target.value as any })}
+              className="border-b-2 w-full py-3 px-0 font-light leading-tight focus:outline-none transition-all cursor-pointer"
+              style={{
+                backgroundColor: 'var(--color-luxury-cream)',
+                color: 'var(--color-luxury-black)',
+                borderBottomColor: 'var(--color-luxury-light-gray)'
+              }}
+              onFocus={(e) => e.target.style.borderBottomColor = 'var(--color-luxury-gold)'}
+              onBlur={(e) => e.target.style.borderBottomColor = 'var(--color-luxury-light-gray)'}
+            >
+              {Object.keys(Currency).map((currency) => (
+                <option key={currency} value={currency}>
+                  {currency === Currency.Native ? 'Native currency' : 'Tokens'}
+                </option>
+              ))}
+            </select>
+          </div>
+        )}
+
         {isNewUser && (
           <>
             <div className="mb-5">
@@ -1,7 +1,8 @@
 import { useState, useCallback, useEffect } from 'react';
 import { getContract, getApi, stringToBytes } from '../utils/contract';
 import { useWallet } from './useWallet';
-import { SubscriptionType, Roles } from '../utils/constants';
+import { SubscriptionType, BillingPeriod, Currency, Roles } from '../utils/constants';
+import type { StreamSchedule } from '../utils/constants';
 
 export function useContract() {
   const { selectedAccount, getInjector, refreshBalance } = useWallet();
@@ -26,10 +27,47 @@ export function useContract() {
     init();
   }, []);
 
+  // Value to send with a payable call paid in native currency, `0` to pay in tokens.
+  // The contract prices are in EVM units, the call value is in native units.
+  const nativeValue = async (
+    category: string,
+    subscriptionType: SubscriptionType,
+    period: BillingPeriod,
+    currency: Currency
+  ): Promise<bigint> => {
+    if (currency === Currency.Token) {
+      return 0n;
+    }
+
+    const contract = await getContract();
+    const api = await getApi();
+    const gasLimit = api.registry.createType('WeightV2', { refTime: 100000000000, proofSize: 10000000 }) as any;
+
+    const { result, output } = await contract.query.getNativeSubscriptionPrice(
+      selectedAccount!.address,
+      { gasLimit },
+      { [category]: null },
+      { [subscriptionType]: null },
+      { [period]: null }
+    );
+    const price = result.isOk && output ? (output.toJSON() as any)?.ok : null;
+    if (price === null || price === undefined) {
+      throw new Error('This tier can only be paid in tokens');
+    }
+
+    // U256 is encoded as four little-endian u64 limbs
+    const limbs: any[] = Array.isArray(price) ? price : price[0] ?? [price];
+    const wei = limbs.reduce((acc: bigint, limb: any, i: number) => acc + (BigInt(limb) << BigInt(64 * i)), 0n);
+    const ratio = BigInt((contract.abi.json as any).spec?.environment?.nativeToEthRatio ?? 1);
+    return wei / ratio;
+  };
+
   // Create new subscription
   const createSubscription = useCallback(async (
     name: string,
     subscriptionType: SubscriptionType,
+    period: BillingPeriod,
+    currency: Currency,
     institutional: boolean,
     school: boolean
   ) => {
@@ -67,8 +105,12 @@ export function useContract() {
         }
         console.log('=== END MAPPING DEBUG ===');
 
-        // Convert subscription type to enum format
+        // New members pay the standard price until the council verifies their category
+        const value = await nativeValue('Standard', subscriptionType, period, currency);
+
+        // Convert subscription type and billing period to enum format
         const subType = { [subscriptionType]: null };
+        const billingPeriod = { [period]: null };
 
         // Call contract method with increased gas limit
         const gasLimit = api.registry.createType('WeightV2', {
@@ -80,9 +122,10 @@ export function useContract() {
         const storageDepositLimit = 500n * 1000000000000n; // 500 tokens
 
         const unsub = await contract.tx.newSubscription(
-          { gasLimit, storageDepositLimit },
+          { gasLimit, storageDepositLimit, value },
           stringToBytes(name),
           subType,
+          billingPeriod,
           institutional,
           school
         ).signAndSend(
@@ -198,6 +241,54 @@ export function useContract() {
     }
   }, [selectedAccount, getInjector, refreshBalance]);
 
+  // Renew subscription for another billing period
+  const renewSubscription = useCallback(async (period: BillingPeriod, currency: Currency) => {
+    if (!selectedAccount) {
+      throw new Error('No account selected');
+    }
+
+    setIsLoading(true);
+    setError(null);
+
+    try {
+      const contract = await getContract();
+      const injector = await getInjector();
+      const api = await getApi();
+
+      const gasLimit = api.registry.createType('WeightV2', { refTime: 100000000000, proofSize: 10000000 }) as any;
+      const storageDepositLimit = 500n * 1000000000000n;
+
+      // The renewal is priced for the member's verified category and current tier
+      const { output } = await contract.query.getMySubscription(selectedAccount.address, { gasLimit });
+      const user = (output?.toJSON() as any)?.ok;
+      if (!user) {
+        throw new Error('No subscription to renew');
+      }
+      const category = user.institutional ? 'Institutional' : user.school ? 'School' : 'Standard';
+      const subscriptionType = user.subscription.subscriptionType as SubscriptionType;
+      const value = await nativeValue(category, subscriptionType, period, currency);
+
+      await contract.tx.renewSubscription(
+        { gasLimit, storageDepositLimit, value },
+        { [period]: null }
+      ).signAndSend(
+        selectedAccount.address,
+        { signer: injector.signer },
+        (result) => {
+          if (result.status.isFinalized) {
+            setIsLoading(false);
+            refreshBalance();
+          }
+        }
+      );
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : 'Transaction failed';
+      setError(errorMessage);
+      setIsLoading(false);
+      throw err;
+    }
+  }, [selectedAccount, getInjector, refreshBalance]);
+
   // Request role
   const requestRole = useCallback(async (role: Roles, description: string) => {
     if (!selectedAccount) {
@@ -242,6 +333,8 @@ export function useContract() {
   const requestSpending = useCallback(async (
     beneficiary: string,
     amount: bigint,
+    currency: Currency,
+    stream: StreamSchedule | null,
     description: string
   ) => {
     if (!selectedAccount) {
@@ -263,6 +356,8 @@ export function useContract() {
         { gasLimit, storageDepositLimit },
         beneficiary,
         amount,
+        { [currency]: null },
+        stream,
         stringToBytes(description)
       ).signAndSend(
         selectedAccount.address,
@@ -417,6 +512,7 @@ export function useContract() {
     contract,
     createSubscription,
     updateSubscription,
+    renewSubscription,
     requestRole,
     requestSpending,
     executeProposal,
@@ -1,7 +1,7 @@
 import { useState } from 'react';
 import { useContract } from '../hooks/useContract';
 import { useWallet } from '../hooks/useWallet';
-import { Roles } from '../utils/constants';
+import { Roles, Currency } from '../utils/constants';
 import { useNotification } from '../components/NotificationProvider';
 
 export function Governance() {
@@ -19,6 +19,11 @@ export function Governance() {
   const [spendingRequest, setSpendingRequest] = useState({
     beneficiary: '',
     amount: '',
+    currency: Currency.Token as Currency,
+    streamed: false,
+    streamStart: '',
+    streamEnd: '',
+    streamCliff: '',
     description: ''
   });
 
@@ -47,13 +52,31 @@ export function Governance() {
 
     try {
       const amount = BigInt(spendingRequest.amount);
+      const stream = spendingRequest.streamed
+        ? {
+            start: Number(spendingRequest.streamStart),
+            end: Number(spendingRequest.streamEnd),
+            cliff: spendingRequest.streamCliff ? Number(spendingRequest.streamCliff) : null
+          }
+        : null;
       await requestSpending(
         spendingRequest.beneficiary,
         amount,
+        spendingRequest.currency,
+        stream,
         spendingRequest.description
       );
       showNotification('Spending proposal submitted successfully', 'success');
-      setSpendingRequest({ beneficiary: '', amount: '', description: '' });
+      setSpendingRequest({
+        beneficiary: '',
+        amount: '',
+        currency: Currency.Token,
+        streamed: false,
+        streamStart: '',
+        streamEnd: '',
+        streamCliff: '',
+        description: ''
+      });
     } catch (err: any) {
       console.error('Failed to request spending:', err);
 
@@ -272,7 +295,7 @@ export function Governance() {
 
                 <div className="mb-8">
                   <label className="block text-sm tracking-widest uppercase mb-4" style={{ color: 'var(--color-edh-gray)', letterSpacing: '0.15em' }}>
-                    Amount (Tokens)
+                    Amount
                   </label>
                   <input
                     type="number"
@@ -292,6 +315,117 @@ export function Governance() {
                   />
                 </div>
 
+                <div className="mb-8">
+                  <label className="block text-sm tracking-widest uppercase mb-4" style={{ color: 'var(--color-edh-gray)', letterSpacing: '0.15em' }}>
+                    Currency
+                  </label>
+                  <select
+                    value={spendingRequest.currency}
+                    onChange={(e) => setSpendingRequest({ ...spendingRequest, currency: e.target.value as any })}
+                    className="border-b w-full py-3 px-0 font-light text-xl leading-tight focus:outline-none transition-all cursor-pointer"
+                    style={{
+                      backgroundColor: 'transparent',
+                      color: 'var(--color-edh-black)',
+                      borderBottomColor: 'var(--color-edh-gray-light)'
+                    }}
+                    onFocus={(e) => e.target.style.borderBottomColor = 'var(--color-edh-black)'}
+                    onBlur={(e) => e.target.style.borderBottomColor = 'var(--color-edh-gray-light)'}
+                  >
+                    {Object.keys(Currency).map((currency) => (
+                      <option key={currency} value={currency}>
+                        {currency}
+                      </option>
+                    ))}
+                  </select>
+                </div>
+
+                <div className="mb-8">
+                  <label className="flex items-center cursor-pointer group">
+                    <input
+                      type="checkbox"
+                      checked={spendingRequest.streamed}
+                      onChange={(e) => setSpendingRequest({ ...spendingRequest, streamed: e.target.checked })}
+                      className="mr-4 h-5 w-5 border cursor-pointer"
+                      style={{
+                        accentColor: 'var(--color-edh-black)',
+                        borderColor: 'var(--color-edh-gray-light)'
+                      }}
+                    />
+                    <span className="text-sm font-light tracking-wide group-hover:opacity-75 transition-opacity" style={{ color: 'var(--color-edh-black)' }}>
+                      Vest the amount over a schedule
+                    </span>
+                  </label>
+                </div>
+
+                {spendingRequest.streamed && (
+                  <div className="mb-8">
+                    <div className="mb-6">
+                      <label className="block text-sm tracking-widest uppercase mb-4" style={{ color: 'var(--color-edh-gray)', letterSpacing: '0.15em' }}>
+                        Start Block
+                      </label>
+                      <input
+                        type="number"
+                        value={spendingRequest.streamStart}
+                        onChange={(e) => setSpendingRequest({ ...spendingRequest, streamStart: e.target.value })}
+                        className="border-b w-full py-3 px-0 font-light text-xl leading-tight focus:outline-none transition-all"
+                        style={{
+                          backgroundColor: 'transparent',
+                          color: 'var(--color-edh-black)',
+                          borderBottomColor: 'var(--color-edh-gray-light)'
+                        }}
+                        onFocus={(e) => e.target.style.borderBottomColor = 'var(--color-edh-black)'}
+                        onBlur={(e) => e.target.style.borderBottomColor = 'var(--color-edh-gray-light)'}
+                        required
+                        placeholder="100000"
+                        min="0"
+                      />
+                    </div>
+
+                    <div className="mb-6">
+                      <label className="block text-sm tracking-widest uppercase mb-4" style={{ color: 'var(--color-edh-gray)', letterSpacing: '0.15em' }}>
+                        End Block
+                      </label>
+                      <input
+                        type="number"
+                        value={spendingRequest.streamEnd}
+                        onChange={(e) => setSpendingRequest({ ...spendingRequest, streamEnd: e.target.value })}
+                        className="border-b w-full py-3 px-0 font-light text-xl leading-tight focus:outline-none transition-all"
+                        style={{
+                          backgroundColor: 'transparent',
+                          color: 'var(--color-edh-black)',
+                          borderBottomColor: 'var(--color-edh-gray-light)'
+                        }}
+                        onFocus={(e) => e.target.style.borderBottomColor = 'var(--color-edh-black)'}
+                        onBlur={(e) => e.target.style.borderBottomColor = 'var(--color-edh-gray-light)'}
+                        required
+                        placeholder="200000"
+                        min="0"
+                      />
+                    </div>
+
+                    <div className="mb-6">
+                      <label className="block text-sm tracking-widest uppercase mb-4" style={{ color: 'var(--color-edh-gray)', letterSpacing: '0.15em' }}>
+                        Cliff Block (optional)
+                      </label>
+                      <input
+                        type="number"
+                        value={spendingRequest.streamCliff}
+                        onChange={(e) => setSpendingRequest({ ...spendingRequest, streamCliff: e.target.value })}
+                        className="border-b w-full py-3 px-0 font-light text-xl leading-tight focus:outline-none transition-all"
+                        style={{
+                          backgroundColor: 'transparent',
+                          color: 'var(--color-edh-black)',
+                          borderBottomColor: 'var(--color-edh-gray-light)'
+                        }}
+                        onFocus={(e) => e.target.style.borderBottomColor = 'var(--color-edh-black)'}
+                        onBlur={(e) => e.target.style.borderBottomColor = 'var(--color-edh-gray-light)'}
+                        placeholder="150000"
+                        min="0"
+                      />
+                    </div>
+                  </div>
+                )}
+
                 <div className="mb-12">
                   <label className="block text-sm tracking-widest uppercase mb-4" style={{ color: 'var(--color-edh-gray)', letterSpacing: '0.15em' }}>
                     Justification
@@ -1,12 +1,12 @@
 import { useState, useEffect } from 'react';
 import { useContract } from '../hooks/useContract';
 import { useWallet } from '../hooks/useWallet';
-import { SubscriptionType, SUBSCRIPTION_AMOUNTS } from '../utils/constants';
+import { SubscriptionType, BillingPeriod, Currency, SUBSCRIPTION_AMOUNTS } from '../utils/constants';
 import { useNotification } from '../components/NotificationProvider';
 import { useSubscription } from '../hooks/useSubscription';
 
 export function Membership() {
-  const { createSubscription, updateSubscription, isLoading } = useContract();
+  const { createSubscription, updateSubscription, renewSubscription, isLoading } = useContract();
   const { isConnected } = useWallet();
   const { showNotification } = useNotification();
   const subscriptionStatus = useSubscription();
@@ -14,10 +14,17 @@ export function Membership() {
   const [formData, setFormData] = useState({
     name: '',
     subscriptionType: SubscriptionType.Free,
+    period: BillingPeriod.Monthly as BillingPeriod,
+    currency: Currency.Token as Currency,
     institutional: false,
     school: false
   });
 
+  const [renewal, setRenewal] = useState({
+    period: BillingPeriod.Monthly as BillingPeriod,
+    currency: Currency.Token as Currency
+  });
+
   const [isNewUser, setIsNewUser] = useState(true);
 
   // Automatically set isNewUser based on subscription status
@@ -34,6 +41,8 @@ export function Membership() {
       await createSubscription(
         formData.name,
         formData.subscriptionType,
+        formData.period,
+        formData.currency,
         formData.institutional,
         formData.school
       );
@@ -50,6 +59,8 @@ export function Membership() {
       setFormData({
         name: '',
         subscriptionType: SubscriptionType.Free,
+        period: BillingPeriod.Monthly,
+        currency: Currency.Token,
         institutional: false,
         school: false
       });
@@ -89,6 +100,29 @@ export function Membership() {
     }
   };
 
+  const handleRenewSubscription = async (e: React.FormEvent) => {
+    e.preventDefault();
+
+    try {
+      await renewSubscription(renewal.period, renewal.currency);
+      showNotification('Membership renewed successfully', 'success');
+
+      setTimeout(() => {
+        if (subscriptionStatus.refresh) {
+          subscriptionStatus.refresh();
+        }
+      }, 2000); // Wait 2 seconds for blockchain to finalize
+    } catch (err: any) {
+      console.error('Failed to renew subscription:', err);
+
+      if (err.message?.includes('insufficient') || err.message?.includes('balance')) {
+        showNotification('Insufficient funds to pay transaction fees', 'error');
+      } else {
+        showNotification('Failed to renew membership. Please try again.', 'error');
+      }
+    }
+  };
+
   if (!isConnected) {
     return (
       <div className="min-h-screen flex items-center justify-center px-8" style={{ backgroundColor: 'var(--color-edh-white)' }}>
@@ -241,6 +275,58 @@ export function Membership() {
                 </select>
               </div>
 
+              {isNewUser && (
+                <div className="mb-8">
+                  <label className="block text-sm tracking-widest uppercase mb-4" style={{ color: 'var(--color-edh-gray)', letterSpacing: '0.15em' }}>
+                    Billing Period
+                  </label>
+                  <select
+                    value={formData.period}
+                    onChange={(e) => setFormData({ ...formData, period: e.target.value as any })}
+                    className="border-b w-full py-3 px-0 font-light text-xl leading-tight focus:outline-none transition-all cursor-pointer"
+                    style={{
+                      backgroundColor: 'transparent',
+                      color: 'var(--color-edh-black)',
+                      borderBottomColor: 'var(--color-edh-gray-light)'
+                    }}
+                    onFocus={(e) => e.target.style.borderBottomColor = 'var(--color-edh-black)'}
+                    onBlur={(e) => e.target.style.borderBottomColor = 'var(--color-edh-gray-light)'}
+                  >
+                    {Object.keys(BillingPeriod).map((period) => (
+                      <option key={period} value={period}>
+                        {period}
+                      </option>
+                    ))}
+                  </select>
+                </div>
+              )}
+
+              {isNewUser && (
+                <div className="mb-8">
+                  <label className="block text-sm tracking-widest uppercase mb-4" style={{ color: 'var(--color-edh-gray)', letterSpacing: '0.15em' }}>
+                    Pay With
+                  </label>
+                  <select
+                    value={formData.currency}
+                    onChange={(e) => setFormData({ ...formData, currency: e.target.value as any })}
+                    className="border-b w-full py-3 px-0 font-light text-xl leading-tight focus:outline-none transition-all cursor-pointer"
+                    style={{
+                      backgroundColor: 'transparent',
+                      color: 'var(--color-edh-black)',
+                      borderBottomColor: 'var(--color-edh-gray-light)'
+                    }}
+                    onFocus={(e) => e.target.style.borderBottomColor = 'var(--color-edh-black)'}
+                    onBlur={(e) => e.target.style.borderBottomColor = 'var(--color-edh-gray-light)'}
+                  >
+                    {Object.keys(Currency).map((currency) => (
+                      <option key={currency} value={currency}>
+                        {currency === Currency.Native ? 'Native currency' : 'Tokens'}
+                      </option>
+                    ))}
+                  </select>
+                </div>
+              )}
+
               {isNewUser && (
                 <>
                   <div className="mb-6">
@@ -303,6 +389,80 @@ export function Membership() {
             </div>
           </form>
 
+          {/* Renewal - Only for existing members */}
+          {subscriptionStatus.hasSubscription && (
+            <form onSubmit={handleRenewSubscription} className="mt-20 pt-16 border-t" style={{ borderColor: 'var(--color-edh-gray-light)' }}>
+              <h3 className="text-lg tracking-widest uppercase mb-12 text-center font-bold" style={{ color: 'var(--color-edh-black)', letterSpacing: '0.2em' }}>
+                Renew Membership
+              </h3>
+              <div className="max-w-xl mx-auto mb-12">
+                <div className="mb-8">
+                  <label className="block text-sm tracking-widest uppercase mb-4" style={{ color: 'var(--color-edh-gray)', letterSpacing: '0.15em' }}>
+                    Billing Period
+                  </label>
+                  <select
+                    value={renewal.period}
+                    onChange={(e) => setRenewal({ ...renewal, period: e.target.value as any })}
+                    className="border-b w-full py-3 px-0 font-light text-xl leading-tight focus:outline-none transition-all cursor-pointer"
+                    style={{
+                      backgroundColor: 'transparent',
+                      color: 'var(--color-edh-black)',
+                      borderBottomColor: 'var(--color-edh-gray-light)'
+                    }}
+                    onFocus={(e) => e.target.style.borderBottomColor = 'var(--color-edh-black)'}
+                    onBlur={(e) => e.target.style.borderBottomColor = 'var(--color-edh-gray-light)'}
+                  >
+                    {Object.keys(BillingPeriod).map((period) => (
+                      <option key={period} value={period}>
+                        {period}
+                      </option>
+                    ))}
+                  </select>
+                </div>
+
+                <div className="mb-8">
+                  <label className="block text-sm tracking-widest uppercase mb-4" style={{ color: 'var(--color-edh-gray)', letterSpacing: '0.15em' }}>
+                    Pay With
+                  </label>
+                  <select
+                    value={renewal.currency}
+                    onChange={(e) => setRenewal({ ...renewal, currency: e.target.value as any })}
+                    className="border-b w-full py-3 px-0 font-light text-xl leading-tight focus:outline-none transition-all cursor-pointer"
+                    style={{
+                      backgroundColor: 'transparent',
+                      color: 'var(--color-edh-black)',
+                      borderBottomColor: 'var(--color-edh-gray-light)'
+                    }}
+                    onFocus={(e) => e.target.style.borderBottomColor = 'var(--color-edh-black)'}
+                    onBlur={(e) => e.target.style.borderBottomColor = 'var(--color-edh-gray-light)'}
+                  >
+                    {Object.keys(Currency).map((currency) => (
+                      <option key={currency} value={currency}>
+                        {currency === Currency.Native ? 'Native currency' : 'Tokens'}
+                      </option>
+                    ))}
+                  </select>
+                </div>
+              </div>
+
+              <div className="flex justify-center">
+                <button
+                  type="submit"
+                  disabled={isLoading}
+                  className="py-4 px-12 font-bold tracking-widest uppercase text-xs hover:bg-white hover:text-black border-2 transition-all disabled:opacity-50 disabled:cursor-not-allowed"
+                  style={{
+                    backgroundColor: 'var(--color-edh-black)',
+                    color: 'var(--color-edh-white)',
+                    borderColor: 'var(--color-edh-black)',
+                    letterSpacing: '0.2em'
+                  }}
+                >
+                  {isLoading ? 'Processing...' : 'Renew Membership'}
+                </button>
+              </div>
+            </form>
+          )}
+
           {/* Tier Benefits */}
           <div className="mt-20 pt-16 border-t" style={{ borderColor: 'var(--color-edh-gray-light)' }}>
             <h3 className="text-lg tracking-widest uppercase mb-12 text-center font-bold" style={{ color: 'var(--color-edh-black)', letterSpacing: '0.2em' }}>
@@ -14,6 +14,30 @@ export const SubscriptionType = {
 
 export type SubscriptionType = typeof SubscriptionType[keyof typeof SubscriptionType];
 
+// Billing periods
+export const BillingPeriod = {
+  Monthly: 'Monthly',
+  Quarterly: 'Quarterly',
+  Yearly: 'Yearly'
+} as const;
+
+export type BillingPeriod = typeof BillingPeriod[keyof typeof BillingPeriod];
+
+// Currencies a subscription or a spending can be paid in
+export const Currency = {
+  Token: 'Token',
+  Native: 'Native'
+} as const;
+
+export type Currency = typeof Currency[keyof typeof Currency];
+
+// Vesting schedule of a streamed spending, in block numbers
+export interface StreamSchedule {
+  start: number;
+  end: number;
+  cliff: number | null;
+}
+
 // Role types
 export const Roles = {
   Mentor: 'Mentor',
@@ -2,20 +2,64 @@
 
 #[ink::contract]
 mod dao {
-    use governance::{GovernanceRef, ProposalType};
+    use governance::{
+        Election, GovernanceRef, ProposalType, UpgradeTarget, VotingMode, VotingRules,
+    };
     use ink::codegen::TraitCallBuilder;
+    use ink::env::call::FromAddr;
+    use ink::ToAddr;
     use my_erc20::MyErc20Ref;
     use ink::{prelude::vec::Vec, storage::Mapping, U256, H256};
+    use ink::storage::{traits::ManualKey, Lazy};
 
     pub const MINUTES: BlockNumber = 20;
     pub const HOURS: BlockNumber = MINUTES * 60;
     pub const DAYS: BlockNumber = HOURS * 24;
 
+    /// Number of council seats filled by each election
+    pub const COUNCIL_SEATS: u32 = 5;
+    /// Length of a council term
+    pub const COUNCIL_TERM: BlockNumber = DAYS * 180;
+    /// Minimum time left to nominate candidates and vote when an election is opened
+    pub const ELECTION_PERIOD: BlockNumber = DAYS * 14;
+
+    /// Bounds of the grace windows governance can set in `SubscriptionRules`
+    pub const MIN_GRACE_PERIOD: BlockNumber = DAYS;
+    pub const MAX_GRACE_PERIOD: BlockNumber = DAYS * 60;
+    /// Bounds of the suspension period, unless it is `0`
+    pub const MIN_SUSPENSION_PERIOD: BlockNumber = DAYS * 7;
+    pub const MAX_SUSPENSION_PERIOD: BlockNumber = DAYS * 365;
+
+    /// Maximum number of members returned by one page of the member registry
+    pub const MAX_PAGE_SIZE: u32 = 100;
+
+    /// Maximum length in bytes of a member's name
+    pub const MAX_NAME_LENGTH: usize = 64;
+    /// Maximum length in bytes of a member's bio
+    pub const MAX_BIO_LENGTH: usize = 512;
+    /// Maximum length in bytes of the metadata pointer (IPFS CID or hash)
+    pub const MAX_METADATA_LENGTH: usize = 128;
+    /// Maximum length in bytes of the notes of a mentoring session
+    pub const MAX_NOTES_LENGTH: usize = 256;
+    /// Maximum number of seats in one seat pool
+    pub const MAX_POOL_SEATS: u32 = 200;
+    /// Maximum number of mentees a mentor can accept
+    pub const MAX_MENTEES: u32 = 20;
+
+    /// Version of the storage layout written by this code, see `migrate`
+    pub const STORAGE_VERSION: u32 = 1;
+    /// Storage key of the version marker, kept out of the root struct so that it
+    /// can be read whatever the layout of the root
+    pub const STORAGE_VERSION_KEY: u32 = 0x7665_7273;
+
     #[derive(Debug, Clone)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
     pub struct User {
         name: Vec<u8>,
+        bio: Vec<u8>,
+        // Content-addressed pointer to off-chain metadata (avatar, credentials, ...)
+        metadata: Option<Vec<u8>>,
         subscription: Subscription,
         mentor: bool,
         council: bool,
@@ -30,20 +74,10 @@ mod dao {
             institutional: bool,
             school: bool,
         ) -> Self {
-            let amount = subscription_amount(subscription.subscription_type.clone());
-            let start = ink::env::block_number::<Environment>();
-            let end = start.saturating_add(DAYS * 30); // Example: 30 days subscription
-            let active = true; // Assuming the subscription is active upon creation
-            let subscription = Subscription {
-                amount,
-                start,
-                end,
-                active,
-                subscription_type: subscription.subscription_type,
-            };
-
             Self {
                 name,
+                bio: Vec::new(),
+                metadata: None,
                 subscription,
                 mentor: false,  // Default value for mentor
                 council: false, // Default value for council
@@ -51,6 +85,49 @@ mod dao {
                 school,
             }
         }
+
+        /// Pricing category the member has been verified for
+        pub fn category(&self) -> PricingCategory {
+            if self.institutional {
+                PricingCategory::Institutional
+            } else if self.school {
+                PricingCategory::School
+            } else {
+                PricingCategory::Standard
+            }
+        }
+
+        /// Lifecycle state of the member's subscription at block `now`.
+        /// Verified institutions and schools benefit from a longer grace window.
+        pub fn status_at(&self, now: BlockNumber, rules: &SubscriptionRules) -> SubscriptionStatus {
+            let mut rules = rules.clone();
+            if self.category() != PricingCategory::Standard {
+                rules.grace_period = rules.grace_period.max(rules.verified_grace_period);
+            }
+            self.subscription.status_at(now, &rules)
+        }
+    }
+
+    /// Currency a payment is made in: the DAO's `MyErc20` token,
+    /// or the chain's native balance sent along with a payable message
+    #[derive(Debug, Clone, Default, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum Currency {
+        #[default]
+        Token,
+        Native,
+    }
+
+    /// Price schedule applied to a member, derived from the verified
+    /// `institutional` and `school` flags
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum PricingCategory {
+        Standard,
+        Institutional,
+        School,
     }
 
     #[derive(Debug, Clone)]
@@ -60,8 +137,104 @@ mod dao {
         amount: U256,
         start: BlockNumber,
         end: BlockNumber,
-        active: bool,
+        // Only `Active` or `Cancelled` is stored: `Cancelled` marks a manual
+        // cancellation, the other states are derived from `end` by `status_at`
+        status: SubscriptionStatus,
         subscription_type: SubscriptionType,
+        period: BillingPeriod,
+        // Currency `amount` was paid in, refunds are made in the same currency
+        currency: Currency,
+    }
+
+    impl Subscription {
+        /// A subscription is expired once the current block is past its `end`
+        pub fn is_expired(&self, now: BlockNumber) -> bool {
+            now > self.end
+        }
+
+        /// Computes the lifecycle state of the subscription at block `now`:
+        /// `Active` until `end`, `Grace` during the grace window, then `Suspended`
+        /// until the suspension period runs out (never if it is `0`).
+        pub fn status_at(&self, now: BlockNumber, rules: &SubscriptionRules) -> SubscriptionStatus {
+            if self.status == SubscriptionStatus::Cancelled {
+                return SubscriptionStatus::Cancelled;
+            }
+            let grace_end = self.end.saturating_add(rules.grace_period);
+            if !self.is_expired(now) {
+                SubscriptionStatus::Active
+            } else if now <= grace_end {
+                SubscriptionStatus::Grace
+            } else if rules.suspension_period == 0
+                || now <= grace_end.saturating_add(rules.suspension_period)
+            {
+                SubscriptionStatus::Suspended
+            } else {
+                SubscriptionStatus::Cancelled
+            }
+        }
+
+        /// Part of `amount` paying for the blocks left between `now` and `end`
+        pub fn unused_amount(&self, now: BlockNumber) -> U256 {
+            if self.is_expired(now) || self.end <= self.start {
+                return U256::zero();
+            }
+            let remaining = self.end.saturating_sub(now.max(self.start));
+            let period = self.end.saturating_sub(self.start);
+            self.amount
+                .saturating_mul(U256::from(remaining))
+                .checked_div(U256::from(period))
+                .unwrap_or_default()
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum SubscriptionStatus {
+        /// Paid period is running
+        Active,
+        /// Paid period is over, privileges are kept until the grace window ends
+        Grace,
+        /// Privileges are lost until the member renews, roles are kept
+        Suspended,
+        /// Membership is over and can no longer be renewed
+        Cancelled,
+    }
+
+    /// Rules driving the `Active -> Grace -> Suspended -> Cancelled` lifecycle
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct SubscriptionRules {
+        /// Number of blocks after `end` during which the member keeps their privileges
+        pub grace_period: BlockNumber,
+        /// Number of blocks a member can stay suspended before the subscription
+        /// is cancelled. `0` keeps suspended members forever.
+        pub suspension_period: BlockNumber,
+        /// Grace window granted to verified institutions and schools
+        pub verified_grace_period: BlockNumber,
+    }
+
+    impl Default for SubscriptionRules {
+        fn default() -> Self {
+            Self {
+                grace_period: DAYS * 7,
+                suspension_period: DAYS * 90,
+                verified_grace_period: DAYS * 30,
+            }
+        }
+    }
+
+    impl SubscriptionRules {
+        /// Whether every period stays within the bounds governance can set
+        pub fn is_valid(&self) -> bool {
+            let grace_periods = MIN_GRACE_PERIOD..=MAX_GRACE_PERIOD;
+            grace_periods.contains(&self.grace_period)
+                && grace_periods.contains(&self.verified_grace_period)
+                && (self.suspension_period == 0
+                    || (MIN_SUSPENSION_PERIOD..=MAX_SUSPENSION_PERIOD)
+                        .contains(&self.suspension_period))
+        }
     }
 
     #[derive(Debug, Clone, PartialEq)]
@@ -74,6 +247,36 @@ mod dao {
         Other,
     }
 
+    /// Billing period chosen by a member when subscribing or renewing
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum BillingPeriod {
+        Monthly,
+        Quarterly,
+        Yearly,
+    }
+
+    impl BillingPeriod {
+        /// Number of blocks covered by one payment
+        pub fn blocks(&self) -> BlockNumber {
+            match self {
+                BillingPeriod::Monthly => DAYS * 30,
+                BillingPeriod::Quarterly => DAYS * 90,
+                BillingPeriod::Yearly => DAYS * 365,
+            }
+        }
+
+        /// Number of monthly tier prices paid for one period, before discount
+        pub fn months(&self) -> u32 {
+            match self {
+                BillingPeriod::Monthly => 1,
+                BillingPeriod::Quarterly => 3,
+                BillingPeriod::Yearly => 12,
+            }
+        }
+    }
+
     #[derive(Debug, Clone, PartialEq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
@@ -82,324 +285,3147 @@ mod dao {
         Council,
     }
 
-    /// Defines an event that is emitted
-    /// every time a subscription is created.
-    #[derive(Debug)]
-    #[ink(event)]
-    pub struct SubscriptionCreated {
-        pub who: Option<Address>,
-        pub when: Option<BlockNumber>,
-    }
-    /// Defines the storage of your contract.
-    /// Add new fields to the below struct in order
-    /// to add new static storage fields to your contract.
-    #[ink(storage)]
-    pub struct Dao {
-        members: Mapping<Address, User>,
-        erc20: MyErc20Ref,
-        governance: GovernanceRef,
+    /// Groups of members with their own index in the registry, so that they can
+    /// be counted and listed without scanning every member
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum MemberGroup {
+        Tier(SubscriptionType),
+        Role(Roles),
     }
 
-    impl Dao {
-        /// Constructor that initializes the `bool` value to the given `init_value`.
-        #[ink(constructor)]
-        pub fn new(supply: ink::U256) -> Self {
-            let erc20code_hash =  H256::from_slice(&hex_literal::hex!("5482c4cd758139eaebf1e0c5413b18ceaf0c54da6e9ae994fa333de2822edd67"));
-            let governance_code_hash =  H256::from_slice(&hex_literal::hex!("7788fa424ec461f5074f3e929e965b77262380d0046a225c4add2303b94d2f03"));
-            let erc20_contract = MyErc20Ref::new(supply)
-                .code_hash(erc20code_hash)
-                .endowment(0.into())
-                .salt_bytes(Some([1u8; 32]))
-                .instantiate();
+    impl MemberGroup {
+        const ALL: [MemberGroup; 6] = [
+            MemberGroup::Tier(SubscriptionType::Free),
+            MemberGroup::Tier(SubscriptionType::Basic),
+            MemberGroup::Tier(SubscriptionType::Premium),
+            MemberGroup::Tier(SubscriptionType::Other),
+            MemberGroup::Role(Roles::Mentor),
+            MemberGroup::Role(Roles::Council),
+        ];
 
-            let governance_contract = GovernanceRef::new(50)
-                .code_hash(governance_code_hash) // Replace with actual code hash
-                .endowment(0.into())
-                .salt_bytes(Some([2u8; 32]))
-                .instantiate();
-            Self {
-                members: Mapping::default(),
-                erc20: erc20_contract,
-                governance: governance_contract,
+        /// Whether `member` belongs to the group
+        fn contains(&self, member: &User) -> bool {
+            match self {
+                MemberGroup::Tier(tier) => member.subscription.subscription_type == *tier,
+                MemberGroup::Role(Roles::Mentor) => member.mentor,
+                MemberGroup::Role(Roles::Council) => member.council,
             }
         }
+    }
 
-        /// This message creates a new user's Subscription
-        #[ink(message)]
-        pub fn new_subscription(
-            &mut self,
-            name: Vec<u8>,
-            subscription_type: SubscriptionType,
-            institutional: bool,
-            school: bool,
-        ) -> Result<(), Error> {
-            let caller = self.env().caller();
-            // Get current block number
-            let current_block = self.env().block_number();
-            let amount = subscription_amount(subscription_type.clone());
-            let subscription = Subscription {
-                amount,
-                start: current_block,
-                end: current_block.saturating_add(DAYS * 30), // Example: 30 days subscription
-                active: true,
-                subscription_type,
-            };
-            let dao_account = self.env().address();
-            let call_builder = self.erc20.call_mut();
+    /// Tier change requested by a member and waiting for a referendum
+    #[derive(Debug, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct TierRequest {
+        member: Address,
+        tier: SubscriptionType,
+    }
 
-            let member = self.members.get(caller);
-            if !member.is_none() {
-                return Err(Error::CanUpgradeOnly);
-            }
-            let user = User::new(name, subscription, institutional, school);
+    /// Voting rules change of a proposal type waiting for a referendum
+    #[derive(Debug, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct VotingRulesRequest {
+        proposal_type: ProposalType,
+        rules: VotingRules,
+    }
 
-            self.members.insert(caller, &user);
-            match user.subscription.subscription_type {
-                SubscriptionType::Free => {}
-                SubscriptionType::Basic | SubscriptionType::Premium | SubscriptionType::Other => {
-                    let amount = user.subscription.amount; // Example amount for Basic
-                    // Transfer the amount from the caller to the DAO account using transfer_from
-                    let _transfer_result = call_builder
-                        .transfer_from(caller, dao_account, amount.into())
-                        .ref_time_limit(100000000000)
-                        .proof_size_limit(10000000)
-                        .storage_deposit_limit(500000000000u128.into())
-                        .invoke();
-                }
+    /// Pricing change waiting for a referendum
+    #[derive(Debug, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum PriceRequest {
+        /// New monthly price of a tier in a pricing category
+        Tier {
+            category: PricingCategory,
+            tier: SubscriptionType,
+            currency: Currency,
+            price: U256,
+        },
+        /// New discount, in percent, granted on a billing period
+        Discount { period: BillingPeriod, percent: u8 },
+    }
+
+    /// Pool of subscriptions paid by an institution and handed out to other members
+    #[derive(Debug, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct SeatPool {
+        sponsor: Address,
+        tier: SubscriptionType,
+        period: BillingPeriod,
+        // Number of seats paid for
+        seats: u32,
+        // Members currently holding a seat
+        holders: Vec<Address>,
+        // End of the period paid for, seat holders are subscribed until then
+        end: BlockNumber,
+    }
+
+    /// Mentoring session logged by a mentor and confirmed by the mentee
+    #[derive(Debug, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Session {
+        session_id: u32,
+        mentor: Address,
+        mentee: Address,
+        // Block at which the mentor logged the session
+        logged_at: BlockNumber,
+        // Duration of the session in minutes
+        duration: u32,
+        // Short summary or content hash of the session notes
+        notes: Vec<u8>,
+        mentor_confirmed: bool,
+        mentee_confirmed: bool,
+        // Reward accrued to the mentor once the session was confirmed
+        reward: U256,
+    }
+
+    impl Session {
+        /// A session counts once both parties confirmed it
+        pub fn is_confirmed(&self) -> bool {
+            self.mentor_confirmed && self.mentee_confirmed
+        }
+    }
+
+    /// Treasury-funded compensation of mentors, only changed through
+    /// `MentorRewards` proposals
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct MentorRewardsConfig {
+        /// Amount accrued to a mentor for each confirmed session
+        pub session_rate: U256,
+        /// Length of a budget epoch in blocks
+        pub epoch_length: BlockNumber,
+        /// Maximum amount accrued to all mentors during one epoch
+        pub epoch_budget: U256,
+    }
+
+    impl Default for MentorRewardsConfig {
+        fn default() -> Self {
+            Self {
+                session_rate: U256::zero(),
+                epoch_length: DAYS * 30,
+                epoch_budget: U256::zero(),
             }
-            self.env().emit_event(SubscriptionCreated {
-                who: Some(caller),
-                when: Some(self.env().block_number()),
-            });
-            Ok(())
         }
+    }
 
-        /// This message updates a user's Subscription
-        #[ink(message)]
-        pub fn update_subscription(&mut self, request: SubscriptionType) -> Result<(), Error> {
-            let caller = self.env().caller();
-            let mut member = self.members.get(caller).ok_or(Error::UserNotFound)?;
-            match member.subscription.subscription_type {
-                SubscriptionType::Free => {
-                    // Update to basic
-                    member.subscription.subscription_type = SubscriptionType::Basic;
-                    let amount = subscription_amount(SubscriptionType::Basic); // Example amount for Basic
-                    member.subscription.amount = amount;
-                    member.subscription.start = self.env().block_number();
-                    member.subscription.end = member.subscription.start.saturating_add(DAYS * 30); // Example: 30 days subscription
-                    self.members.insert(caller, &member);
-                    // Transfer the amount from the caller to the DAO account using transfer_from
-                    let dao_account = self.env().address();
-                    let call_builder = self.erc20.call_mut();
+    impl MentorRewardsConfig {
+        /// Epochs must have a length, and a single session cannot exceed the budget
+        /// of a whole epoch
+        pub fn is_valid(&self) -> bool {
+            self.epoch_length > 0 && self.session_rate <= self.epoch_budget
+        }
+    }
 
-                    let _transfer_result = call_builder
-                        .transfer_from(caller, dao_account, amount.into())
-                        .ref_time_limit(100000000000)
-                        .proof_size_limit(10000000)
-                        .storage_deposit_limit(500000000000u128.into())
-                        .invoke();
-                }
-                SubscriptionType::Basic => {
-                    let amount = subscription_amount(request.clone()); // Example amount for Basic
-                    match request {
-                        SubscriptionType::Premium => {
-                            member.subscription.subscription_type = request;
-                            member.subscription.amount = amount;
-                            member.subscription.start = self.env().block_number();
-                            member.subscription.end =
-                                member.subscription.start.saturating_add(DAYS * 30);
-                        }
-                        SubscriptionType::Other => {
-                            member.subscription.subscription_type = request;
-                            member.subscription.amount = amount;
-                            member.subscription.start = self.env().block_number();
-                            member.subscription.end =
-                                member.subscription.start.saturating_add(DAYS * 30);
-                        }
-                        _ => return Err(Error::InvalidSubscription),
-                    }
-                    self.members.insert(caller, &member);
-                    // Transfer the amount from the caller to the DAO account using transfer_from
-                    let dao_account = self.env().address();
-                    let call_builder = self.erc20.call_mut();
+    /// Voting power granted by membership, only changed through
+    /// `VotingWeights` proposals. Role and category weights add up to the tier weight.
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct VotingWeights {
+        pub free: u32,
+        pub basic: u32,
+        pub premium: u32,
+        pub other: u32,
+        /// Extra weight of mentors
+        pub mentor: u32,
+        /// Extra weight of council members
+        pub council: u32,
+        /// Extra weight of verified institutions
+        pub institutional: u32,
+    }
 
-                    let _transfer_result = call_builder
-                        .transfer_from(caller, dao_account, amount.into())
-                        .ref_time_limit(100000000000)
-                        .proof_size_limit(10000000)
-                        .storage_deposit_limit(500000000000u128.into())
-                        .invoke();
-                }
-                _ => {} // Submit to referendum
+    impl Default for VotingWeights {
+        fn default() -> Self {
+            Self {
+                free: 1,
+                basic: 2,
+                premium: 4,
+                other: 4,
+                mentor: 1,
+                council: 2,
+                institutional: 1,
             }
-            Ok(())
         }
+    }
 
-        /// Request a spending proposal
-        #[ink(message)]
-        pub fn request_spending(
-            &mut self,
-            beneficiary: Address,
-            amount:  U256,
-            description: Vec<u8>,
-        ) -> Result<(), Error> {
-            let caller = self.env().caller();
-            let member = self.members.get(caller).ok_or(Error::UserNotFound)?;
-            if member.council != true && member.mentor != true {
-                return Err(Error::NotAnAuthorisedUser);
-            }
-            // Create a proposal for spending request
-            let call_builder = self.governance.call_mut();
-            let _proposal = call_builder.create_proposal(
-                description,
-                ProposalType::Spending,
-                Some(beneficiary),
-                amount.into(), // Convert Balance to u128
-            );
+    impl VotingWeights {
+        /// Every tier must carry some weight: with no voting power at all, no
+        /// proposal could pass again, not even one restoring the weights
+        pub fn is_valid(&self) -> bool {
+            self.free > 0 && self.basic > 0 && self.premium > 0 && self.other > 0
+        }
 
-            Ok(())
+        /// Voting power of a member under these weights
+        pub fn voting_power(&self, member: &User) -> U256 {
+            let mut weight = match member.subscription.subscription_type {
+                SubscriptionType::Free => self.free,
+                SubscriptionType::Basic => self.basic,
+                SubscriptionType::Premium => self.premium,
+                SubscriptionType::Other => self.other,
+            };
+            if member.mentor {
+                weight = weight.saturating_add(self.mentor);
+            }
+            if member.council {
+                weight = weight.saturating_add(self.council);
+            }
+            if member.category() == PricingCategory::Institutional {
+                weight = weight.saturating_add(self.institutional);
+            }
+            U256::from(weight)
         }
-        /// Request a particular role
-        #[ink(message)]
-        pub fn request_role(&mut self, role: Roles, description: Vec<u8>) -> Result<(), Error> {
-            // This function is a placeholder for requesting a role.
-            let caller = self.env().caller();
-            let member = self.members.get(caller).ok_or(Error::UserNotFound)?;
-            if member.subscription.subscription_type != SubscriptionType::Premium {
-                return Err(Error::NotPremiumUser);
+    }
+
+    /// Vesting schedule of a Spending proposal paid over time
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct StreamSchedule {
+        /// Block from which the amount starts vesting
+        pub start: BlockNumber,
+        /// Block at which the whole amount is vested
+        pub end: BlockNumber,
+        /// Nothing can be withdrawn before this block
+        pub cliff: Option<BlockNumber>,
+    }
+
+    /// Approved Spending proposal paid to its beneficiary as it vests
+    #[derive(Debug, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Stream {
+        beneficiary: Address,
+        amount: U256,
+        withdrawn: U256,
+        currency: Currency,
+        schedule: StreamSchedule,
+        // Set when governance cancels the stream, vesting stops at that block
+        cancelled_at: Option<BlockNumber>,
+    }
+
+    impl Stream {
+        /// Part of `amount` vested at block `now`
+        pub fn vested_amount(&self, now: BlockNumber) -> U256 {
+            let now = self.cancelled_at.map_or(now, |cancelled_at| now.min(cancelled_at));
+            let cliff = self.schedule.cliff.unwrap_or(self.schedule.start);
+            if now < cliff || now <= self.schedule.start {
+                return U256::zero();
             }
-            match role {
-                Roles::Mentor => {
-                    //create a proposal for requesting Mentor role
-                    let call_builder = self.governance.call_mut();
-                    let _proposal = call_builder
-                        .create_proposal(
-                            description,
-                            ProposalType::NewMentor,
-                            Some(caller),
-                            U256::from(0), // Placeholder for proposal parameters
-                        )
-                        .ref_time_limit(1000000)
-                        .proof_size_limit(1000000)
-                        .storage_deposit_limit(1000000.into())
-                        .invoke();
-                }
-                Roles::Council => {
-                    //create a proposal for requesting Mentor role
-                    let call_builder = self.governance.call_mut();
-                    let _proposal = call_builder
-                        .create_proposal(
-                            description,
-                            ProposalType::NewMentor,
-                            Some(caller),
-                            U256::from(0), // Placeholder for proposal parameters
-                        )
-                        .ref_time_limit(1000000)
-                        .proof_size_limit(1000000)
-                        .storage_deposit_limit(1000000.into())
-                        .invoke();
-                }
+            if now >= self.schedule.end {
+                return self.amount;
             }
-            Ok(())
+            let elapsed = now.saturating_sub(self.schedule.start);
+            let duration = self.schedule.end.saturating_sub(self.schedule.start);
+            self.amount
+                .saturating_mul(U256::from(elapsed))
+                .checked_div(U256::from(duration))
+                .unwrap_or_default()
         }
+    }
 
-        /// Execute a proposal
+    /// Payment made by the treasury for an approved Spending proposal
+    #[derive(Debug, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct SpendingRecord {
+        proposal_id: u32,
+        beneficiary: Address,
+        amount: U256,
+        currency: Currency,
+        executed_at: BlockNumber,
+    }
+
+    /// Deployment settings of the DAO and of the `MyErc20` and `Governance`
+    /// contracts it instantiates
+    #[derive(Debug, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct DaoConfig {
+        /// Code hash of the uploaded `MyErc20` contract
+        pub erc20_code_hash: H256,
+        /// Code hash of the uploaded `Governance` contract
+        pub governance_code_hash: H256,
+        /// Salts making the addresses of the instantiated contracts deterministic
+        pub erc20_salt: Option<[u8; 32]>,
+        pub governance_salt: Option<[u8; 32]>,
+        /// Token supply minted to the DAO
+        pub initial_supply: U256,
+        /// Length of the voting window of governance proposals, in blocks
+        pub voting_period: BlockNumber,
+        /// Monthly token prices replacing `default_subscription_amount`
+        pub prices: Vec<(PricingCategory, SubscriptionType, U256)>,
+        /// Monthly native currency prices, tiers left out can only be paid in tokens
+        pub native_prices: Vec<(PricingCategory, SubscriptionType, U256)>,
+        /// Founding council of the first term, at most `COUNCIL_SEATS` accounts.
+        /// Each of them takes their seat when they subscribe.
+        pub initial_council: Vec<Address>,
+    }
+
+    /// Defines an event that is emitted
+    /// every time a subscription is created.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct SubscriptionCreated {
+        pub who: Option<Address>,
+        pub when: Option<BlockNumber>,
+    }
+
+    /// Defines an event that is emitted
+    /// every time a subscription is renewed.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct SubscriptionRenewed {
+        pub who: Option<Address>,
+        pub until: Option<BlockNumber>,
+    }
+
+    /// Defines an event that is emitted
+    /// every time a member cancels their subscription.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct SubscriptionCancelled {
+        pub who: Option<Address>,
+        pub refund: U256,
+    }
+    /// Defines an event that is emitted
+    /// every time governance changes the price of a tier.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct PriceChanged {
+        pub category: PricingCategory,
+        pub tier: SubscriptionType,
+        pub currency: Currency,
+        pub price: U256,
+    }
+
+    /// Defines an event that is emitted
+    /// every time governance changes the discount of a billing period.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DiscountChanged {
+        pub period: BillingPeriod,
+        pub percent: u8,
+    }
+    /// Defines an event that is emitted
+    /// every time the council verifies an institution or a school.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CategoryVerified {
+        pub who: Option<Address>,
+        pub category: PricingCategory,
+    }
+    /// Defines an event that is emitted
+    /// every time an institution buys a pool of seats.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct SeatPoolCreated {
+        pub pool_id: u32,
+        pub sponsor: Option<Address>,
+        pub seats: u32,
+    }
+
+    /// Defines an event that is emitted
+    /// every time a seat is assigned to a member.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct SeatAssigned {
+        pub pool_id: u32,
+        pub who: Option<Address>,
+    }
+
+    /// Defines an event that is emitted
+    /// every time a seat is taken back from a member.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct SeatRevoked {
+        pub pool_id: u32,
+        pub who: Option<Address>,
+    }
+    /// Defines an event that is emitted
+    /// every time a member updates their profile.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProfileUpdated {
+        pub who: Option<Address>,
+    }
+    /// Defines an event that is emitted
+    /// every time a role is taken back from a member.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RoleRevoked {
+        pub who: Option<Address>,
+        pub role: Roles,
+    }
+    /// Defines an event that is emitted
+    /// every time the council is refreshed from an election.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CouncilElected {
+        pub election_id: Option<u32>,
+        pub members: Vec<Address>,
+        pub term_end: BlockNumber,
+    }
+    /// Defines an event that is emitted
+    /// every time a mentor accepts a mentee.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MentorshipStarted {
+        pub mentor: Option<Address>,
+        pub mentee: Option<Address>,
+    }
+
+    /// Defines an event that is emitted
+    /// every time a mentorship ends.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MentorshipEnded {
+        pub mentor: Option<Address>,
+        pub mentee: Option<Address>,
+    }
+
+    /// Defines an event that is emitted
+    /// every time a mentor logs a session.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct SessionLogged {
+        pub session_id: u32,
+        pub mentor: Option<Address>,
+        pub mentee: Option<Address>,
+    }
+
+    /// Defines an event that is emitted
+    /// every time a mentee confirms a session.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct SessionConfirmed {
+        pub session_id: u32,
+    }
+    /// Defines an event that is emitted
+    /// every time a mentor claims their rewards.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MentorRewardsClaimed {
+        pub mentor: Option<Address>,
+        pub amount: U256,
+    }
+    /// Defines an event that is emitted
+    /// every time the treasury pays a Spending proposal.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct SpendingExecuted {
+        pub proposal_id: u32,
+        pub beneficiary: Option<Address>,
+        pub amount: U256,
+    }
+    /// Defines an event that is emitted
+    /// every time governance changes the voting weights.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct VotingWeightsChanged {
+        pub weights: VotingWeights,
+    }
+
+    /// Defines an event that is emitted
+    /// every time an approved Upgrade proposal replaces the code of a contract.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct Upgraded {
+        pub target: UpgradeTarget,
+        pub code_hash: H256,
+    }
+
+    /// Defines an event that is emitted
+    /// every time the storage is migrated after an upgrade.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct Migrated {
+        pub from: u32,
+        pub to: u32,
+    }
+
+    /// Defines an event that is emitted
+    /// every time an approved Spending proposal opens a stream.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct StreamCreated {
+        pub stream_id: u32,
+        pub beneficiary: Option<Address>,
+        pub amount: U256,
+        pub end: BlockNumber,
+    }
+
+    /// Defines an event that is emitted
+    /// every time a beneficiary withdraws from a stream.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct StreamWithdrawn {
+        pub stream_id: u32,
+        pub beneficiary: Option<Address>,
+        pub amount: U256,
+    }
+
+    /// Defines an event that is emitted
+    /// every time governance cancels a stream.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct StreamCancelled {
+        pub stream_id: u32,
+        pub released: U256,
+    }
+    /// Defines the storage of your contract.
+    /// Add new fields to the below struct in order
+    /// to add new static storage fields to your contract.
+    #[ink(storage)]
+    pub struct Dao {
+        members: Mapping<Address, User>,
+        archived_members: Mapping<Address, User>,
+        // Pending tier changes, keyed by the id of their governance proposal
+        tier_requests: Mapping<u32, TierRequest>,
+        // Approved tier changes of members paying in native currency, applied once
+        // they pay the difference with `settle_tier_change`
+        owed_tier_changes: Mapping<Address, SubscriptionType>,
+        // Monthly price of each tier per pricing category, only changed through `PriceChange` proposals
+        prices: Mapping<(PricingCategory, SubscriptionType), U256>,
+        // Monthly price in native currency, tiers without one can only be paid in tokens
+        native_prices: Mapping<(PricingCategory, SubscriptionType), U256>,
+        // Discount in percent of each billing period, only changed through `PriceChange` proposals
+        period_discounts: Mapping<BillingPeriod, u8>,
+        // Pending price changes, keyed by the id of their governance proposal
+        price_requests: Mapping<u32, PriceRequest>,
+        // Institutional or school status claimed by a member and waiting for council approval
+        category_requests: Mapping<Address, PricingCategory>,
+        // Seat pools bought by institutions
+        seat_pools: Mapping<u32, SeatPool>,
+        // Number of seat pools created so far
+        seat_pool_count: u32,
+        // Pool holding the seat of each sponsored member
+        seat_holders: Mapping<Address, u32>,
+        // Subscription a seat holder had before the seat was assigned, given back
+        // when the seat is released
+        seat_previous: Mapping<Address, Subscription>,
+        // Enumerable index of the members: position -> address
+        member_list: Mapping<u32, Address>,
+        // Position of each member in `member_list`
+        member_positions: Mapping<Address, u32>,
+        // Number of members in the index
+        member_count: u32,
+        // Index of each member group: (group, position) -> address
+        group_members: Mapping<(MemberGroup, u32), Address>,
+        // Position of each member in the index of their groups
+        group_positions: Mapping<(MemberGroup, Address), u32>,
+        // Number of members in each group
+        group_counts: Mapping<MemberGroup, u32>,
+        // Members currently holding a council seat
+        council_members: Vec<Address>,
+        // Block at which the current council term ends
+        council_term_end: BlockNumber,
+        // Election collecting nominations for the next term, if any
+        next_election: Option<u32>,
+        // Maximum number of mentees each mentor accepts
+        mentor_capacity: Mapping<Address, u32>,
+        // Mentees of each mentor
+        mentees: Mapping<Address, Vec<Address>>,
+        // Mentor of each mentee
+        mentor_of: Mapping<Address, Address>,
+        // Mentor requested by each mentee, waiting for the mentor to accept
+        mentor_requests: Mapping<Address, Address>,
+        // Mentoring sessions
+        sessions: Mapping<u32, Session>,
+        // Number of sessions logged so far
+        session_count: u32,
+        // Sessions logged by each mentor, indexed in logging order
+        mentor_sessions: Mapping<(Address, u32), u32>,
+        // Number of sessions logged by each mentor
+        mentor_session_counts: Mapping<Address, u32>,
+        // Compensation paid to mentors for confirmed sessions
+        mentor_rewards_config: MentorRewardsConfig,
+        // Pending compensation changes, keyed by the id of their governance proposal
+        mentor_rewards_requests: Mapping<u32, MentorRewardsConfig>,
+        // Voting power of each tier and role in governance proposals
+        voting_weights: VotingWeights,
+        // Pending voting weight changes, keyed by the id of their governance proposal
+        voting_weights_requests: Mapping<u32, VotingWeights>,
+        // Pending voting mode changes, keyed by the id of their governance proposal
+        voting_mode_requests: Mapping<u32, VotingMode>,
+        // Pending quorum and approval threshold changes, keyed by proposal id
+        voting_rules_requests: Mapping<u32, VotingRulesRequest>,
+        // Pending subscription lifecycle rules, keyed by the id of their governance proposal
+        subscription_rules_requests: Mapping<u32, SubscriptionRules>,
+        // Rewards accrued and not claimed yet by each mentor
+        mentor_rewards: Mapping<Address, U256>,
+        // Block at which the current budget epoch started
+        epoch_start: BlockNumber,
+        // Part of the current epoch budget not accrued to mentors yet
+        epoch_budget_left: U256,
+        // Treasury funds owed and not paid out yet (unclaimed mentor rewards, ...)
+        committed_funds: U256,
+        // Same as `committed_funds`, for the native balance of the DAO
+        committed_native_funds: U256,
+        // Payments made for Spending proposals, keyed by proposal id
+        spending_ledger: Mapping<u32, SpendingRecord>,
+        // Proposal ids of the payments in `spending_ledger`, in execution order
+        spending_history: Mapping<u32, u32>,
+        // Number of payments in `spending_history`
+        spending_count: u32,
+        // Vesting schedules of pending Spending proposals, keyed by proposal id
+        stream_requests: Mapping<u32, StreamSchedule>,
+        // Pending Spending proposals paid in native currency, keyed by proposal id
+        spending_currencies: Mapping<u32, Currency>,
+        // Streams created by approved Spending proposals, keyed by proposal id
+        streams: Mapping<u32, Stream>,
+        // Pending stream cancellations: proposal id -> stream id
+        stream_cancellations: Mapping<u32, u32>,
+        erc20: MyErc20Ref,
+        governance: GovernanceRef,
+        subscription_rules: SubscriptionRules,
+        // Version of the storage layout, bumped by `migrate` after an upgrade
+        storage_version: Lazy<u32, ManualKey<STORAGE_VERSION_KEY>>,
+    }
+
+    impl Dao {
+        /// Instantiates the `MyErc20` and `Governance` contracts described by `config`
+        #[ink(constructor)]
+        pub fn new(config: DaoConfig) -> Self {
+            let erc20_contract = MyErc20Ref::new(config.initial_supply)
+                .code_hash(config.erc20_code_hash)
+                .endowment(0.into())
+                .salt_bytes(config.erc20_salt)
+                .instantiate();
+
+            let governance_contract = GovernanceRef::new(config.voting_period)
+                .code_hash(config.governance_code_hash)
+                .endowment(0.into())
+                .salt_bytes(config.governance_salt)
+                .instantiate();
+
+            Self::with_contracts(
+                erc20_contract,
+                governance_contract,
+                config.prices,
+                config.native_prices,
+                config.initial_council,
+            )
+        }
+
+        /// Connects to `MyErc20` and `Governance` contracts that are already deployed.
+        /// The governance contract must then hand its DAO role over with `set_dao`.
+        #[ink(constructor)]
+        pub fn from_addresses(
+            erc20: Address,
+            governance: Address,
+            prices: Vec<(PricingCategory, SubscriptionType, U256)>,
+            native_prices: Vec<(PricingCategory, SubscriptionType, U256)>,
+            initial_council: Vec<Address>,
+        ) -> Self {
+            Self::with_contracts(
+                MyErc20Ref::from_addr(erc20),
+                GovernanceRef::from_addr(governance),
+                prices,
+                native_prices,
+                initial_council,
+            )
+        }
+
+        /// Initial state shared by the constructors. Tiers missing from `prices`
+        /// keep their `default_subscription_amount`.
+        fn with_contracts(
+            erc20: MyErc20Ref,
+            governance: GovernanceRef,
+            prices: Vec<(PricingCategory, SubscriptionType, U256)>,
+            native_prices: Vec<(PricingCategory, SubscriptionType, U256)>,
+            mut initial_council: Vec<Address>,
+        ) -> Self {
+            initial_council.sort();
+            initial_council.dedup();
+            initial_council.truncate(COUNCIL_SEATS as usize);
+            let mut initial_prices = Mapping::default();
+            for category in [
+                PricingCategory::Standard,
+                PricingCategory::Institutional,
+                PricingCategory::School,
+            ] {
+                for tier in [
+                    SubscriptionType::Free,
+                    SubscriptionType::Basic,
+                    SubscriptionType::Premium,
+                    SubscriptionType::Other,
+                ] {
+                    let price = default_subscription_amount(category.clone(), tier.clone());
+                    initial_prices.insert((&category, &tier), &price);
+                }
+            }
+            for (category, tier, price) in prices {
+                initial_prices.insert((&category, &tier), &price);
+            }
+            let mut initial_native_prices = Mapping::default();
+            for (category, tier, price) in native_prices {
+                initial_native_prices.insert((&category, &tier), &price);
+            }
+            let mut dao = Self {
+                members: Mapping::default(),
+                archived_members: Mapping::default(),
+                tier_requests: Mapping::default(),
+                owed_tier_changes: Mapping::default(),
+                prices: initial_prices,
+                native_prices: initial_native_prices,
+                period_discounts: Mapping::default(),
+                price_requests: Mapping::default(),
+                category_requests: Mapping::default(),
+                seat_pools: Mapping::default(),
+                seat_pool_count: 0,
+                seat_holders: Mapping::default(),
+                seat_previous: Mapping::default(),
+                member_list: Mapping::default(),
+                member_positions: Mapping::default(),
+                member_count: 0,
+                group_members: Mapping::default(),
+                group_positions: Mapping::default(),
+                group_counts: Mapping::default(),
+                council_members: initial_council,
+                council_term_end: Self::env().block_number().saturating_add(COUNCIL_TERM),
+                next_election: None,
+                mentor_capacity: Mapping::default(),
+                mentees: Mapping::default(),
+                mentor_of: Mapping::default(),
+                mentor_requests: Mapping::default(),
+                sessions: Mapping::default(),
+                session_count: 0,
+                mentor_sessions: Mapping::default(),
+                mentor_session_counts: Mapping::default(),
+                mentor_rewards_config: MentorRewardsConfig::default(),
+                mentor_rewards_requests: Mapping::default(),
+                voting_weights: VotingWeights::default(),
+                voting_weights_requests: Mapping::default(),
+                voting_mode_requests: Mapping::default(),
+                voting_rules_requests: Mapping::default(),
+                subscription_rules_requests: Mapping::default(),
+                mentor_rewards: Mapping::default(),
+                epoch_start: 0,
+                epoch_budget_left: U256::zero(),
+                committed_funds: U256::zero(),
+                committed_native_funds: U256::zero(),
+                spending_ledger: Mapping::default(),
+                spending_history: Mapping::default(),
+                spending_count: 0,
+                stream_requests: Mapping::default(),
+                spending_currencies: Mapping::default(),
+                streams: Mapping::default(),
+                stream_cancellations: Mapping::default(),
+                erc20,
+                governance,
+                subscription_rules: SubscriptionRules::default(),
+                storage_version: Lazy::new(),
+            };
+            dao.storage_version.set(&STORAGE_VERSION);
+            dao
+        }
+
+        /// This message creates a new user's Subscription.
+        /// `institutional` and `school` are only claims: the member pays the standard
+        /// price until the council verifies them with `verify_category`.
+        /// The subscription is paid in native currency when a value is sent with the
+        /// call, otherwise in tokens the caller has approved the DAO to spend.
+        #[ink(message, payable)]
+        pub fn new_subscription(
+            &mut self,
+            name: Vec<u8>,
+            subscription_type: SubscriptionType,
+            period: BillingPeriod,
+            institutional: bool,
+            school: bool,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if name.len() > MAX_NAME_LENGTH {
+                return Err(Error::ProfileFieldTooLong);
+            }
+            // Get current block number
+            let current_block = self.env().block_number();
+            let category_request = requested_category(institutional, school)?;
+
+            // A cancelled member can join again from scratch
+            if let Some(member) = self.members.get(caller) {
+                if member.status_at(current_block, &self.subscription_rules)
+                    != SubscriptionStatus::Cancelled
+                {
+                    return Err(Error::CanUpgradeOnly);
+                }
+            }
+            // Collect the payment from the caller before registering the member
+            let (amount, currency) = self.take_payment(
+                caller,
+                &PricingCategory::Standard,
+                &subscription_type,
+                &period,
+            )?;
+            let subscription = Subscription {
+                amount,
+                start: current_block,
+                end: current_block.saturating_add(period.blocks()),
+                status: SubscriptionStatus::Active,
+                subscription_type,
+                period,
+                currency,
+            };
+            let mut user = User::new(name, subscription, false, false);
+            // Members of the founding council take their seat as they join
+            user.council = self.council_members.contains(&caller);
+
+            self.members.insert(caller, &user);
+            self.index_member(caller);
+            self.sync_voter(caller)?;
+            match category_request {
+                Some(category) => {
+                    self.category_requests.insert(caller, &category);
+                }
+                None => self.category_requests.remove(caller),
+            }
+            self.env().emit_event(SubscriptionCreated {
+                who: Some(caller),
+                when: Some(self.env().block_number()),
+            });
+            Ok(())
+        }
+
+        /// This message updates a user's Subscription.
+        /// Free members move to Basic for a new period. Basic members move to Premium
+        /// or Other for the rest of their period, paying the difference as in an approved
+        /// tier change. Changes from Premium or Other are submitted to a referendum.
+        #[ink(message)]
+        pub fn update_subscription(&mut self, request: SubscriptionType) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut member = self.members.get(caller).ok_or(Error::UserNotFound)?;
+            // The subscription of a seat holder is managed by the sponsor
+            if self.seat_holders.contains(caller) {
+                return Err(Error::SeatAlreadyAssigned);
+            }
+            match member.subscription.subscription_type {
+                SubscriptionType::Free => {
+                    if request != SubscriptionType::Basic {
+                        return Err(Error::InvalidSubscription);
+                    }
+                    member.subscription.subscription_type = SubscriptionType::Basic;
+                    member.subscription.currency = Currency::Token;
+                    let amount = self.period_price(
+                        &member.category(),
+                        &SubscriptionType::Basic,
+                        &member.subscription.period,
+                    );
+                    member.subscription.amount = amount;
+                    member.subscription.start = self.env().block_number();
+                    member.subscription.end = member
+                        .subscription
+                        .start
+                        .saturating_add(member.subscription.period.blocks());
+                    // Transfer the amount from the caller to the DAO account using transfer_from
+                    self.collect_payment(caller, amount)?;
+                    self.members.insert(caller, &member);
+                    self.refresh_groups(caller);
+                    self.sync_voter(caller)?;
+                }
+                SubscriptionType::Basic => {
+                    if request != SubscriptionType::Premium && request != SubscriptionType::Other {
+                        return Err(Error::InvalidSubscription);
+                    }
+                    self.apply_tier_change(TierRequest {
+                        member: caller,
+                        tier: request,
+                    })?;
+                }
+                SubscriptionType::Premium | SubscriptionType::Other => {
+                    if request == member.subscription.subscription_type {
+                        return Err(Error::InvalidSubscription);
+                    }
+                    // Submit to referendum, the change is applied by `execute_proposal`
+                    let amount =
+                        self.period_price(&member.category(), &request, &member.subscription.period);
+                    let call_builder = self.governance.call_mut();
+                    let proposal_id = call_builder
+                        .create_proposal(
+                            b"Subscription tier change".to_vec(),
+                            ProposalType::TierChange,
+                            Some(caller),
+                            amount,
+                            caller,
+                        )
+                        .ref_time_limit(1000000)
+                        .proof_size_limit(1000000)
+                        .storage_deposit_limit(1000000.into())
+                        .invoke()
+                        .map_err(Error::GovernanceFailed)?;
+                    self.tier_requests.insert(
+                        proposal_id,
+                        &TierRequest {
+                            member: caller,
+                            tier: request,
+                        },
+                    );
+                }
+            }
+            Ok(())
+        }
+
+        /// This message renews the caller's Subscription for another billing period.
+        /// An active subscription or one in its grace window is extended from its
+        /// current `end`, a suspended one starts a new period from the current block.
+        /// Like `new_subscription`, a value sent with the call pays in native currency.
+        /// A running subscription must be extended in the currency it was paid in.
+        #[ink(message, payable)]
+        pub fn renew_subscription(&mut self, period: BillingPeriod) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let current_block = self.env().block_number();
+            let mut member = self.members.get(caller).ok_or(Error::UserNotFound)?;
+            // The subscription of a seat holder is managed by the sponsor
+            if self.seat_holders.contains(caller) {
+                return Err(Error::SeatAlreadyAssigned);
+            }
+            let (amount, currency) = self.take_payment(
+                caller,
+                &member.category(),
+                &member.subscription.subscription_type,
+                &period,
+            )?;
+            match member.status_at(current_block, &self.subscription_rules) {
+                SubscriptionStatus::Active | SubscriptionStatus::Grace => {
+                    if currency != member.subscription.currency {
+                        return Err(Error::CurrencyMismatch);
+                    }
+                    member.subscription.amount = member.subscription.amount.saturating_add(amount);
+                    member.subscription.end =
+                        member.subscription.end.saturating_add(period.blocks());
+                }
+                SubscriptionStatus::Suspended => {
+                    member.subscription.amount = amount;
+                    member.subscription.start = current_block;
+                    member.subscription.end = current_block.saturating_add(period.blocks());
+                    member.subscription.currency = currency;
+                }
+                SubscriptionStatus::Cancelled => return Err(Error::SubscriptionCancelled),
+            }
+            member.subscription.status = SubscriptionStatus::Active;
+            member.subscription.period = period;
+            self.members.insert(caller, &member);
+            self.sync_voter(caller)?;
+            self.env().emit_event(SubscriptionRenewed {
+                who: Some(caller),
+                until: Some(member.subscription.end),
+            });
+            Ok(())
+        }
+
+        /// This message cancels the caller's Subscription.
+        /// The member is moved to the archive and the part of the subscription
+        /// amount covering the blocks left until `end` is refunded from the DAO balance.
+        #[ink(message)]
+        pub fn cancel_subscription(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let current_block = self.env().block_number();
+            let mut member = self.members.get(caller).ok_or(Error::UserNotFound)?;
+            // A seat holder is refunded for the subscription they paid themselves
+            if let Some(previous) = self.release_seat(caller) {
+                member.subscription = previous;
+            }
+            let refund = member.subscription.unused_amount(current_block);
+
+            member.subscription.status = SubscriptionStatus::Cancelled;
+            member.subscription.end = member.subscription.end.min(current_block);
+            // Transfer the refund from the DAO balance back to the member
+            self.send_funds(caller, refund, &member.subscription.currency)?;
+            self.members.remove(caller);
+            self.unindex_member(caller);
+            self.remove_voter(caller)?;
+            self.council_members.retain(|account| *account != caller);
+            self.owed_tier_changes.remove(caller);
+            self.archived_members.insert(caller, &member);
+            self.env().emit_event(SubscriptionCancelled {
+                who: Some(caller),
+                refund,
+            });
+            Ok(())
+        }
+
+        /// Pays, in native currency, the difference owed for an approved tier change
+        /// of a subscription paid natively, and moves the caller to the new tier.
+        /// The value sent must cover the difference at the current block, any surplus
+        /// is sent back.
+        #[ink(message, payable)]
+        pub fn settle_tier_change(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let tier = self
+                .owed_tier_changes
+                .get(caller)
+                .ok_or(Error::NothingToSettle)?;
+            let member = self.active_member(caller)?;
+            if member.subscription.currency != Currency::Native {
+                return Err(Error::CurrencyMismatch);
+            }
+            let (credit, cost) =
+                self.tier_change_amounts(&member, &tier, self.env().block_number());
+            let owed = cost.saturating_sub(credit);
+            let paid = self.env().transferred_value();
+            if paid < owed {
+                return Err(Error::InvalidSubscriptionAmount);
+            }
+            self.owed_tier_changes.remove(caller);
+            let surplus = paid
+                .saturating_sub(owed)
+                .saturating_add(credit.saturating_sub(cost));
+            self.send_native(caller, surplus)?;
+            self.set_tier(caller, member, tier, cost)
+        }
+
+        /// Get the tier change `account` still has to pay for with `settle_tier_change`
+        #[ink(message)]
+        pub fn get_owed_tier_change(&self, account: Address) -> Option<SubscriptionType> {
+            self.owed_tier_changes.get(account)
+        }
+
+        /// This message updates the caller's profile.
+        /// `metadata` is an optional content-addressed pointer (IPFS CID or hash)
+        /// to off-chain data such as an avatar or credentials.
+        #[ink(message)]
+        pub fn update_profile(
+            &mut self,
+            name: Vec<u8>,
+            bio: Vec<u8>,
+            metadata: Option<Vec<u8>>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut member = self.members.get(caller).ok_or(Error::UserNotFound)?;
+            if name.len() > MAX_NAME_LENGTH
+                || bio.len() > MAX_BIO_LENGTH
+                || metadata
+                    .as_ref()
+                    .is_some_and(|metadata| metadata.len() > MAX_METADATA_LENGTH)
+            {
+                return Err(Error::ProfileFieldTooLong);
+            }
+            member.name = name;
+            member.bio = bio;
+            member.metadata = metadata;
+            self.members.insert(caller, &member);
+            self.env().emit_event(ProfileUpdated { who: Some(caller) });
+            Ok(())
+        }
+
+        /// Verified institutions buy `seats` subscriptions of a tier for one billing period,
+        /// up to `MAX_POOL_SEATS`.
+        /// The seats are then handed out to other members with `assign_seat`.
+        #[ink(message)]
+        pub fn create_seat_pool(
+            &mut self,
+            tier: SubscriptionType,
+            period: BillingPeriod,
+            seats: u32,
+        ) -> Result<u32, Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if member.category() != PricingCategory::Institutional {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            if tier == SubscriptionType::Free || seats == 0 || seats > MAX_POOL_SEATS {
+                return Err(Error::InvalidSubscription);
+            }
+            let amount = self
+                .period_price(&PricingCategory::Institutional, &tier, &period)
+                .saturating_mul(U256::from(seats));
+            // Transfer the price of all the seats from the sponsor to the DAO account
+            self.collect_payment(caller, amount)?;
+
+            let pool_id = self.seat_pool_count;
+            let pool = SeatPool {
+                sponsor: caller,
+                tier,
+                end: self.env().block_number().saturating_add(period.blocks()),
+                period,
+                seats,
+                holders: Vec::new(),
+            };
+            self.seat_pools.insert(pool_id, &pool);
+            self.seat_pool_count += 1;
+
+            self.env().emit_event(SeatPoolCreated {
+                pool_id,
+                sponsor: Some(caller),
+                seats,
+            });
+            Ok(pool_id)
+        }
+
+        /// The sponsor pays for another billing period of all the seats of a pool.
+        /// Every seat holder's subscription is renewed until the new end of the pool.
+        #[ink(message)]
+        pub fn renew_seat_pool(&mut self, pool_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut pool = self.seat_pools.get(pool_id).ok_or(Error::SeatPoolNotFound)?;
+            if pool.sponsor != caller {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            let current_block = self.env().block_number();
+            let amount = self
+                .period_price(&PricingCategory::Institutional, &pool.tier, &pool.period)
+                .saturating_mul(U256::from(pool.seats));
+            // Transfer the price of all the seats from the sponsor to the DAO account
+            self.collect_payment(caller, amount)?;
+
+            pool.end = pool.end.max(current_block).saturating_add(pool.period.blocks());
+            for holder in pool.holders.iter() {
+                if let Some(mut member) = self.members.get(holder) {
+                    member.subscription.end = member.subscription.end.max(pool.end);
+                    member.subscription.status = SubscriptionStatus::Active;
+                    self.members.insert(holder, &member);
+                    // Holders whose seat had lapsed get their voting power back
+                    self.sync_voter(*holder)?;
+                }
+            }
+            self.seat_pools.insert(pool_id, &pool);
+            Ok(())
+        }
+
+        /// The sponsor hands a seat of the pool to `account`.
+        /// The account is subscribed at the pool's tier until the end of the pool
+        /// without holding any token. A member's own subscription is set aside and
+        /// given back when the seat is released.
+        #[ink(message)]
+        pub fn assign_seat(
+            &mut self,
+            pool_id: u32,
+            account: Address,
+            name: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut pool = self.seat_pools.get(pool_id).ok_or(Error::SeatPoolNotFound)?;
+            if pool.sponsor != caller {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            if name.len() > MAX_NAME_LENGTH {
+                return Err(Error::ProfileFieldTooLong);
+            }
+            if self.seat_holders.contains(account) {
+                return Err(Error::SeatAlreadyAssigned);
+            }
+            if pool.holders.len() as u32 >= pool.seats {
+                return Err(Error::NoSeatAvailable);
+            }
+            let current_block = self.env().block_number();
+            if pool.end <= current_block {
+                return Err(Error::SeatPoolExpired);
+            }
+            // The seat is paid by the sponsor, nothing is refundable to the member
+            let subscription = Subscription {
+                amount: U256::zero(),
+                start: current_block,
+                end: pool.end,
+                status: SubscriptionStatus::Active,
+                subscription_type: pool.tier.clone(),
+                period: pool.period.clone(),
+                currency: Currency::Token,
+            };
+            let member = match self.members.get(account) {
+                Some(mut member)
+                    if member.status_at(current_block, &self.subscription_rules)
+                        != SubscriptionStatus::Cancelled =>
+                {
+                    self.seat_previous.insert(account, &member.subscription);
+                    member.subscription = subscription;
+                    member
+                }
+                _ => User::new(name, subscription, false, false),
+            };
+            self.members.insert(account, &member);
+            self.index_member(account);
+            self.sync_voter(account)?;
+            pool.holders.push(account);
+            self.seat_pools.insert(pool_id, &pool);
+            self.seat_holders.insert(account, &pool_id);
+            self.env().emit_event(SeatAssigned {
+                pool_id,
+                who: Some(account),
+            });
+            Ok(())
+        }
+
+        /// The sponsor takes a seat back and the seat can be assigned again.
+        /// The member gets back the subscription they had before the seat, or their
+        /// subscription ends at the current block if they had none.
+        #[ink(message)]
+        pub fn revoke_seat(&mut self, pool_id: u32, account: Address) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let pool = self.seat_pools.get(pool_id).ok_or(Error::SeatPoolNotFound)?;
+            if pool.sponsor != caller {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            if self.seat_holders.get(account) != Some(pool_id) {
+                return Err(Error::SeatNotAssigned);
+            }
+            self.end_seat(pool_id, account)
+        }
+
+        /// Releases the seats of a pool its sponsor did not renew, giving the holders
+        /// back the subscription they had before. Anyone can call it once the pool ended.
+        #[ink(message)]
+        pub fn release_expired_seats(&mut self, pool_id: u32) -> Result<(), Error> {
+            let pool = self.seat_pools.get(pool_id).ok_or(Error::SeatPoolNotFound)?;
+            if pool.end > self.env().block_number() {
+                return Err(Error::SeatPoolNotExpired);
+            }
+            for holder in pool.holders {
+                self.end_seat(pool_id, holder)?;
+            }
+            Ok(())
+        }
+
+        /// Request a spending proposal, paid in tokens or in native currency.
+        /// With a `stream`, the amount is not paid at once but vests over the schedule
+        /// and the beneficiary pulls it with `withdraw_stream`.
+        #[ink(message)]
+        pub fn request_spending(
+            &mut self,
+            beneficiary: Address,
+            amount:  U256,
+            currency: Currency,
+            stream: Option<StreamSchedule>,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council && !member.mentor {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            if let Some(schedule) = &stream {
+                let cliff = schedule.cliff.unwrap_or(schedule.start);
+                if schedule.end <= schedule.start || cliff < schedule.start || cliff > schedule.end {
+                    return Err(Error::InvalidStream);
+                }
+            }
+            // Create a proposal for spending request
+            let call_builder = self.governance.call_mut();
+            let proposal_id = call_builder
+                .create_proposal(
+                    description,
+                    ProposalType::Spending,
+                    Some(beneficiary),
+                    amount.into(), // Convert Balance to u128
+                    caller,
+                )
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            if let Some(schedule) = stream {
+                self.stream_requests.insert(proposal_id, &schedule);
+            }
+            if currency == Currency::Native {
+                self.spending_currencies.insert(proposal_id, &currency);
+            }
+
+            Ok(())
+        }
+
+        /// Beneficiaries withdraw the vested part of a stream
+        #[ink(message)]
+        pub fn withdraw_stream(&mut self, stream_id: u32) -> Result<U256, Error> {
+            let caller = self.env().caller();
+            let mut stream = self.streams.get(stream_id).ok_or(Error::StreamNotFound)?;
+            if stream.beneficiary != caller {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            let amount = stream
+                .vested_amount(self.env().block_number())
+                .saturating_sub(stream.withdrawn);
+            if amount == U256::zero() {
+                return Err(Error::NothingToClaim);
+            }
+            stream.withdrawn = stream.withdrawn.saturating_add(amount);
+            self.streams.insert(stream_id, &stream);
+            self.release_funds(&stream.currency, amount);
+            self.send_funds(caller, amount, &stream.currency)?;
+            self.env().emit_event(StreamWithdrawn {
+                stream_id,
+                beneficiary: Some(caller),
+                amount,
+            });
+            Ok(amount)
+        }
+
+        /// Request the cancellation of the unvested part of a stream
+        #[ink(message)]
+        pub fn request_stream_cancellation(
+            &mut self,
+            stream_id: u32,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council && !member.mentor {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            let stream = self.streams.get(stream_id).ok_or(Error::StreamNotFound)?;
+            if stream.cancelled_at.is_some() {
+                return Err(Error::StreamCancelled);
+            }
+            let call_builder = self.governance.call_mut();
+            let proposal_id = call_builder
+                .create_proposal(
+                    description,
+                    ProposalType::CancelStream,
+                    Some(stream.beneficiary),
+                    stream.amount,
+                    caller,
+                )
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            self.stream_cancellations.insert(proposal_id, &stream_id);
+            Ok(())
+        }
+        /// Request a change of the price of a tier in a pricing category, in tokens
+        /// or in native currency. Members keep the price they paid until their next renewal.
+        #[ink(message)]
+        pub fn request_price_change(
+            &mut self,
+            category: PricingCategory,
+            tier: SubscriptionType,
+            currency: Currency,
+            price: U256,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            let call_builder = self.governance.call_mut();
+            let proposal_id = call_builder
+                .create_proposal(description, ProposalType::PriceChange, None, price, caller)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            self.price_requests.insert(
+                proposal_id,
+                &PriceRequest::Tier {
+                    category,
+                    tier,
+                    currency,
+                    price,
+                },
+            );
+            Ok(())
+        }
+
+        /// Request a change of the discount, in percent, granted on a billing period
+        #[ink(message)]
+        pub fn request_discount_change(
+            &mut self,
+            period: BillingPeriod,
+            percent: u8,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            if percent > 100 {
+                return Err(Error::InvalidDiscount);
+            }
+            let call_builder = self.governance.call_mut();
+            let proposal_id = call_builder
+                .create_proposal(
+                    description,
+                    ProposalType::PriceChange,
+                    None,
+                    U256::from(percent),
+                    caller,
+                )
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            self.price_requests
+                .insert(proposal_id, &PriceRequest::Discount { period, percent });
+            Ok(())
+        }
+
+        /// Request a particular role.
+        /// Mentor requests go to a referendum, Council requests nominate the caller
+        /// in the election for the next council term.
+        #[ink(message)]
+        pub fn request_role(&mut self, role: Roles, description: Vec<u8>) -> Result<(), Error> {
+            // This function is a placeholder for requesting a role.
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if member.subscription.subscription_type != SubscriptionType::Premium {
+                return Err(Error::NotPremiumUser);
+            }
+            match role {
+                Roles::Mentor => {
+                    //create a proposal for requesting Mentor role
+                    let call_builder = self.governance.call_mut();
+                    let _proposal = call_builder
+                        .create_proposal(
+                            description,
+                            ProposalType::NewMentor,
+                            Some(caller),
+                            U256::from(0), // Placeholder for proposal parameters
+                            caller,
+                        )
+                        .ref_time_limit(1000000)
+                        .proof_size_limit(1000000)
+                        .storage_deposit_limit(1000000.into())
+                        .invoke()
+                        .map_err(Error::GovernanceFailed)?;
+                }
+                Roles::Council => {
+                    // Nominate the caller in the next council election
+                    let election_id = self.ensure_next_election()?;
+                    let call_builder = self.governance.call_mut();
+                    call_builder
+                        .nominate(election_id, caller)
+                        .ref_time_limit(1000000)
+                        .proof_size_limit(1000000)
+                        .storage_deposit_limit(1000000.into())
+                        .invoke()
+                        .map_err(Error::GovernanceFailed)?;
+                }
+            }
+            Ok(())
+        }
+
+        /// Vote for a candidate of the next council election.
+        /// Only active Premium members can vote, once per election.
+        #[ink(message)]
+        pub fn vote_council(&mut self, candidate: Address) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if member.subscription.subscription_type != SubscriptionType::Premium {
+                return Err(Error::NotPremiumUser);
+            }
+            let election_id = self.next_election.ok_or(Error::NoElectionRunning)?;
+            let call_builder = self.governance.call_mut();
+            call_builder
+                .vote_election(election_id, caller, candidate)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)
+        }
+
+        /// Vote on a governance proposal. Active members vote through the DAO,
+        /// which forwards the vote with the voting power of their membership.
+        #[ink(message)]
+        pub fn vote(&mut self, proposal_id: u32, approve: bool) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.active_member(caller)?;
+            let call_builder = self.governance.call_mut();
+            call_builder
+                .vote_for(caller, proposal_id, approve)
+                .ref_time_limit(100000000000)
+                .proof_size_limit(10000000)
+                .storage_deposit_limit(500000000000u128.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)
+        }
+
+        /// Starts a new council term once the current one is over.
+        /// Anyone can call it: the council is replaced by the winners of the election,
+        /// or kept for another term if nobody was nominated.
+        #[ink(message)]
+        pub fn refresh_council(&mut self) -> Result<(), Error> {
+            let current_block = self.env().block_number();
+            if current_block <= self.council_term_end {
+                return Err(Error::CouncilTermNotEnded);
+            }
+            let election_id = self.next_election;
+            let mut elected = Vec::new();
+            if let Some(election_id) = election_id {
+                let call_builder = self.governance.call_mut();
+                elected = call_builder
+                    .close_election(election_id)
+                    .ref_time_limit(1000000)
+                    .proof_size_limit(1000000)
+                    .storage_deposit_limit(1000000.into())
+                    .invoke()
+                    .map_err(Error::GovernanceFailed)?;
+            }
+            // Elected members must still be active Premium members to take their seat
+            elected.retain(|candidate| {
+                self.active_member(*candidate).is_ok_and(|member| {
+                    member.subscription.subscription_type == SubscriptionType::Premium
+                })
+            });
+
+            if !elected.is_empty() {
+                for account in self.council_members.clone() {
+                    if let Some(mut member) = self.members.get(account) {
+                        member.council = false;
+                        self.members.insert(account, &member);
+                        self.refresh_groups(account);
+                        self.sync_voter(account)?;
+                    }
+                }
+                for account in elected.iter() {
+                    if let Some(mut member) = self.members.get(account) {
+                        member.council = true;
+                        self.members.insert(account, &member);
+                        self.refresh_groups(*account);
+                        self.sync_voter(*account)?;
+                    }
+                }
+                self.council_members = elected;
+            }
+            self.council_term_end = current_block.saturating_add(COUNCIL_TERM);
+            self.next_election = None;
+            self.env().emit_event(CouncilElected {
+                election_id,
+                members: self.council_members.clone(),
+                term_end: self.council_term_end,
+            });
+            Ok(())
+        }
+
+        /// Mentors set how many mentees they accept, up to `MAX_MENTEES`
+        #[ink(message)]
+        pub fn set_mentor_capacity(&mut self, capacity: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.active_mentor(caller)?;
+            if capacity > MAX_MENTEES {
+                return Err(Error::InvalidMentorCapacity);
+            }
+            self.mentor_capacity.insert(caller, &capacity);
+            Ok(())
+        }
+
+        /// Ask a mentor to take the caller as a mentee
+        #[ink(message)]
+        pub fn request_mentor(&mut self, mentor: Address) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.active_member(caller)?;
+            if self.mentor_of.contains(caller) {
+                return Err(Error::AlreadyMentored);
+            }
+            let mentor_member = self.members.get(mentor).ok_or(Error::UserNotFound)?;
+            if !mentor_member.mentor || mentor == caller {
+                return Err(Error::NotAMentor);
+            }
+            self.mentor_requests.insert(caller, &mentor);
+            Ok(())
+        }
+
+        /// Mentors accept a pending request, within their capacity
+        #[ink(message)]
+        pub fn accept_mentee(&mut self, mentee: Address) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.active_mentor(caller)?;
+            if self.mentor_requests.get(mentee) != Some(caller) {
+                return Err(Error::MentorshipRequestNotFound);
+            }
+            let mut mentees = self.mentees.get(caller).unwrap_or_default();
+            let capacity = self.mentor_capacity.get(caller).unwrap_or_default();
+            if mentees.len() as u32 >= capacity {
+                return Err(Error::MentorAtCapacity);
+            }
+            mentees.push(mentee);
+            self.mentees.insert(caller, &mentees);
+            self.mentor_of.insert(mentee, &caller);
+            self.mentor_requests.remove(mentee);
+            self.env().emit_event(MentorshipStarted {
+                mentor: Some(caller),
+                mentee: Some(mentee),
+            });
+            Ok(())
+        }
+
+        /// Either party ends a mentorship. The session history is kept.
+        #[ink(message)]
+        pub fn end_mentorship(&mut self, other: Address) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let (mentor, mentee) = if self.mentor_of.get(caller) == Some(other) {
+                (other, caller)
+            } else if self.mentor_of.get(other) == Some(caller) {
+                (caller, other)
+            } else {
+                return Err(Error::NotYourMentee);
+            };
+            let mut mentees = self.mentees.get(mentor).unwrap_or_default();
+            mentees.retain(|account| *account != mentee);
+            self.mentees.insert(mentor, &mentees);
+            self.mentor_of.remove(mentee);
+            self.env().emit_event(MentorshipEnded {
+                mentor: Some(mentor),
+                mentee: Some(mentee),
+            });
+            Ok(())
+        }
+
+        /// Mentors log a session held with one of their mentees.
+        /// The session counts once the mentee confirms it.
+        #[ink(message)]
+        pub fn log_session(
+            &mut self,
+            mentee: Address,
+            duration: u32,
+            notes: Vec<u8>,
+        ) -> Result<u32, Error> {
+            let caller = self.env().caller();
+            self.active_mentor(caller)?;
+            if self.mentor_of.get(mentee) != Some(caller) {
+                return Err(Error::NotYourMentee);
+            }
+            if notes.len() > MAX_NOTES_LENGTH {
+                return Err(Error::ProfileFieldTooLong);
+            }
+            let session_id = self.session_count;
+            let session = Session {
+                session_id,
+                mentor: caller,
+                mentee,
+                logged_at: self.env().block_number(),
+                duration,
+                notes,
+                mentor_confirmed: true,
+                mentee_confirmed: false,
+                reward: U256::zero(),
+            };
+            self.sessions.insert(session_id, &session);
+            self.session_count += 1;
+            let logged = self.mentor_session_counts.get(caller).unwrap_or_default();
+            self.mentor_sessions.insert((caller, logged), &session_id);
+            self.mentor_session_counts.insert(caller, &(logged + 1));
+            self.env().emit_event(SessionLogged {
+                session_id,
+                mentor: Some(caller),
+                mentee: Some(mentee),
+            });
+            Ok(session_id)
+        }
+
+        /// Mentees confirm a session logged by their mentor.
+        /// The mentor accrues the session rate, within the budget left for the epoch.
+        #[ink(message)]
+        pub fn confirm_session(&mut self, session_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut session = self.sessions.get(session_id).ok_or(Error::SessionNotFound)?;
+            if session.mentee != caller {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            if session.mentee_confirmed {
+                return Err(Error::SessionAlreadyConfirmed);
+            }
+            session.mentee_confirmed = true;
+            session.reward = self.accrue_mentor_reward(session.mentor);
+            self.sessions.insert(session_id, &session);
+            self.env().emit_event(SessionConfirmed { session_id });
+            Ok(())
+        }
+
+        /// Mentors withdraw the rewards accrued for their confirmed sessions
+        #[ink(message)]
+        pub fn claim_mentor_rewards(&mut self) -> Result<U256, Error> {
+            let caller = self.env().caller();
+            let amount = self.mentor_rewards.get(caller).unwrap_or_default();
+            if amount == U256::zero() {
+                return Err(Error::NothingToClaim);
+            }
+            self.mentor_rewards.remove(caller);
+            self.committed_funds = self.committed_funds.saturating_sub(amount);
+            self.send_payment(caller, amount)?;
+            self.env().emit_event(MentorRewardsClaimed {
+                mentor: Some(caller),
+                amount,
+            });
+            Ok(amount)
+        }
+
+        /// Request a change of the session rate and epoch budget paid to mentors
+        #[ink(message)]
+        pub fn request_mentor_rewards_change(
+            &mut self,
+            config: MentorRewardsConfig,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            if !config.is_valid() {
+                return Err(Error::InvalidRewardsConfig);
+            }
+            let call_builder = self.governance.call_mut();
+            let proposal_id = call_builder
+                .create_proposal(
+                    description,
+                    ProposalType::MentorRewards,
+                    None,
+                    config.session_rate,
+                    caller,
+                )
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            self.mentor_rewards_requests.insert(proposal_id, &config);
+            Ok(())
+        }
+
+        /// Request a change of the voting power granted to each tier and role.
+        /// Once approved, voters are updated with `sync_voters`.
+        #[ink(message)]
+        pub fn request_voting_weights_change(
+            &mut self,
+            weights: VotingWeights,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            if !weights.is_valid() {
+                return Err(Error::InvalidVotingWeights);
+            }
+            let call_builder = self.governance.call_mut();
+            let proposal_id = call_builder
+                .create_proposal(
+                    description,
+                    ProposalType::VotingWeights,
+                    None,
+                    U256::zero(),
+                    caller,
+                )
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            self.voting_weights_requests.insert(proposal_id, &weights);
+            Ok(())
+        }
+
+        /// Request to count votes with the `MyErc20` balance of the voters at the block
+        /// each proposal was created, or back with the membership voting weights
+        #[ink(message)]
+        pub fn request_voting_mode_change(
+            &mut self,
+            token_balance: bool,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            let voting_mode = if token_balance {
+                VotingMode::TokenBalance {
+                    token: self.erc20.to_addr(),
+                }
+            } else {
+                VotingMode::Membership
+            };
+            let call_builder = self.governance.call_mut();
+            let proposal_id = call_builder
+                .create_proposal(description, ProposalType::VotingMode, None, U256::zero(), caller)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            self.voting_mode_requests.insert(proposal_id, &voting_mode);
+            Ok(())
+        }
+
+        /// Request a change of the quorum, in percent of the total voting power, and of
+        /// the approval threshold that proposals of `proposal_type` must reach
+        #[ink(message)]
+        pub fn request_voting_rules_change(
+            &mut self,
+            proposal_type: ProposalType,
+            rules: VotingRules,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            if rules.quorum_percent > 100 {
+                return Err(Error::InvalidVotingRules);
+            }
+            let call_builder = self.governance.call_mut();
+            let proposal_id = call_builder
+                .create_proposal(description, ProposalType::VotingRules, None, U256::zero(), caller)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            self.voting_rules_requests.insert(
+                proposal_id,
+                &VotingRulesRequest {
+                    proposal_type,
+                    rules,
+                },
+            );
+            Ok(())
+        }
+
+        /// Updates the voting power of a page of the member registry after the voting
+        /// weights changed, and drops the power of members whose subscription lapsed.
+        /// `limit` is capped to `MAX_PAGE_SIZE`, anyone can call it.
+        #[ink(message)]
+        pub fn sync_voters(&mut self, offset: u32, limit: u32) -> Result<(), Error> {
+            for account in self.list_members(offset, limit) {
+                self.sync_voter(account)?;
+            }
+            Ok(())
+        }
+
+        /// Request the removal of a role from any member
+        #[ink(message)]
+        pub fn request_role_removal(
+            &mut self,
+            role: Roles,
+            target: Address,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council && !member.mentor {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            let target_member = self.members.get(target).ok_or(Error::UserNotFound)?;
+            let proposal_type = match role {
+                Roles::Mentor if target_member.mentor => ProposalType::RemoveMentor,
+                Roles::Council if target_member.council => ProposalType::RemoveCouncil,
+                _ => return Err(Error::RoleNotHeld),
+            };
+            let call_builder = self.governance.call_mut();
+            let _proposal = call_builder
+                .create_proposal(description, proposal_type, Some(target), U256::from(0), caller)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            Ok(())
+        }
+
+        /// Request the replacement of the code of the DAO, the governance or the token
+        /// contract by the uploaded code `code_hash`
+        #[ink(message)]
+        pub fn request_upgrade(
+            &mut self,
+            target: UpgradeTarget,
+            code_hash: H256,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            let call_builder = self.governance.call_mut();
+            let _proposal = call_builder
+                .create_proposal(
+                    description,
+                    ProposalType::Upgrade { target, code_hash },
+                    None,
+                    U256::zero(),
+                    caller,
+                )
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            Ok(())
+        }
+
+        /// Migrates the storage written by an older code to `STORAGE_VERSION`.
+        /// To be called once the DAO has been upgraded, does nothing if the
+        /// storage is already up to date. Anyone can call it, it only moves data
+        /// forward.
+        ///
+        /// The `Dao` root struct is decoded before any message runs, so an upgrade
+        /// must keep its layout: new state goes under its own key, in a `Mapping`
+        /// or a `Lazy` with a `ManualKey`, and this is where the new code fills it
+        /// from the old data. The version marker itself lives under
+        /// `STORAGE_VERSION_KEY` and reads `0` for storage written before it existed.
+        #[ink(message)]
+        pub fn migrate(&mut self) -> Result<(), Error> {
+            let from = self.get_storage_version();
+            if from >= STORAGE_VERSION {
+                return Ok(());
+            }
+            // Version specific migrations go here, oldest first
+            self.storage_version.set(&STORAGE_VERSION);
+            self.env().emit_event(Migrated {
+                from,
+                to: STORAGE_VERSION,
+            });
+            Ok(())
+        }
+
+        /// Get the version of the storage layout
+        #[ink(message)]
+        pub fn get_storage_version(&self) -> u32 {
+            self.storage_version.get().unwrap_or_default()
+        }
+
+        /// Cancel a proposal the caller requested, at any point before it is executed
+        /// or closed. The change it carried is dropped.
+        #[ink(message)]
+        pub fn cancel_proposal(&mut self, proposal_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.active_member(caller)?;
+            let call_builder = self.governance.call_mut();
+            let proposal = call_builder
+                .get_proposal(proposal_id)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(|_| Error::ProposalNotFound)?;
+            if proposal.owner != caller {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            call_builder
+                .cancel_proposal(proposal_id)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            self.discard_requests(proposal_id);
+            Ok(())
+        }
+
+        /// Execute a proposal
+        #[ink(message)]
+        pub fn execute_proposal(&mut self, proposal_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council && !member.mentor {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            // Execute the proposal
+            let call_builder = self.governance.call_mut();
+            let proposal = call_builder
+                .get_proposal(proposal_id)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(|_| Error::ProposalNotFound)?;
+
+            // Only succeeded proposals can be executed, and only once
+            call_builder
+                .update_proposal_status(proposal_id, true)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            let proposal_type = proposal.basic_infos.proposal_type;
+            // Role proposals target the member recorded as their beneficiary
+            let target = proposal
+                .transaction
+                .as_ref()
+                .map(|transaction| transaction.beneficiary)
+                .unwrap_or(proposal.owner);
+
+            match proposal_type {
+                ProposalType::Spending => {
+                    let transaction = proposal
+                        .transaction
+                        .clone()
+                        .ok_or(Error::InvalidSubscription)?;
+                    self.execute_spending(proposal_id, transaction.beneficiary, transaction.amount)
+                }
+                // Council seats are only filled by elections
+                ProposalType::NewCouncilvoter => Err(Error::CouncilIsElected),
+                ProposalType::NewMentor => {
+                    let mut owner = self.members.get(target).ok_or(Error::UserNotFound)?;
+                    owner.mentor = true;
+                    self.members.insert(target, &owner);
+                    self.refresh_groups(target);
+                    self.sync_voter(target)
+                }
+                ProposalType::RemoveMentor => {
+                    let mut owner = self.members.get(target).ok_or(Error::UserNotFound)?;
+                    owner.mentor = false;
+                    self.members.insert(target, &owner);
+                    self.refresh_groups(target);
+                    self.sync_voter(target)?;
+                    self.env().emit_event(RoleRevoked {
+                        who: Some(target),
+                        role: Roles::Mentor,
+                    });
+                    Ok(())
+                }
+                ProposalType::RemoveCouncil => {
+                    let mut owner = self.members.get(target).ok_or(Error::UserNotFound)?;
+                    owner.council = false;
+                    self.members.insert(target, &owner);
+                    self.refresh_groups(target);
+                    self.sync_voter(target)?;
+                    self.council_members.retain(|account| *account != target);
+                    self.env().emit_event(RoleRevoked {
+                        who: Some(target),
+                        role: Roles::Council,
+                    });
+                    Ok(())
+                }
+                ProposalType::Upgrade { target, code_hash } => {
+                    self.execute_upgrade(target, code_hash)
+                }
+                ProposalType::CancelStream => {
+                    let stream_id = self
+                        .stream_cancellations
+                        .get(proposal_id)
+                        .ok_or(Error::ProposalNotFound)?;
+                    self.stream_cancellations.remove(proposal_id);
+                    self.cancel_stream(stream_id)
+                }
+                ProposalType::VotingWeights => {
+                    let weights = self
+                        .voting_weights_requests
+                        .get(proposal_id)
+                        .ok_or(Error::ProposalNotFound)?;
+                    self.voting_weights_requests.remove(proposal_id);
+                    self.voting_weights = weights.clone();
+                    self.env().emit_event(VotingWeightsChanged { weights });
+                    Ok(())
+                }
+                ProposalType::VotingMode => {
+                    let voting_mode = self
+                        .voting_mode_requests
+                        .get(proposal_id)
+                        .ok_or(Error::ProposalNotFound)?;
+                    self.voting_mode_requests.remove(proposal_id);
+                    let call_builder = self.governance.call_mut();
+                    call_builder
+                        .set_voting_mode(voting_mode)
+                        .ref_time_limit(1000000)
+                        .proof_size_limit(1000000)
+                        .storage_deposit_limit(1000000.into())
+                        .invoke()
+                        .map_err(Error::GovernanceFailed)
+                }
+                ProposalType::SubscriptionRules => {
+                    let rules = self
+                        .subscription_rules_requests
+                        .get(proposal_id)
+                        .ok_or(Error::ProposalNotFound)?;
+                    self.subscription_rules_requests.remove(proposal_id);
+                    self.subscription_rules = rules;
+                    Ok(())
+                }
+                ProposalType::VotingRules => {
+                    let request = self
+                        .voting_rules_requests
+                        .get(proposal_id)
+                        .ok_or(Error::ProposalNotFound)?;
+                    self.voting_rules_requests.remove(proposal_id);
+                    let call_builder = self.governance.call_mut();
+                    call_builder
+                        .set_voting_rules(request.proposal_type, request.rules)
+                        .ref_time_limit(1000000)
+                        .proof_size_limit(1000000)
+                        .storage_deposit_limit(1000000.into())
+                        .invoke()
+                        .map_err(Error::GovernanceFailed)
+                }
+                ProposalType::MentorRewards => {
+                    let config = self
+                        .mentor_rewards_requests
+                        .get(proposal_id)
+                        .ok_or(Error::ProposalNotFound)?;
+                    self.mentor_rewards_requests.remove(proposal_id);
+                    self.mentor_rewards_config = config;
+                    // The new length and budget apply from a fresh epoch
+                    self.start_epoch();
+                    Ok(())
+                }
+                ProposalType::TierChange => {
+                    let request = self
+                        .tier_requests
+                        .get(proposal_id)
+                        .ok_or(Error::ProposalNotFound)?;
+                    self.tier_requests.remove(proposal_id);
+                    self.apply_tier_change(request)
+                }
+                ProposalType::PriceChange => {
+                    let request = self
+                        .price_requests
+                        .get(proposal_id)
+                        .ok_or(Error::ProposalNotFound)?;
+                    self.price_requests.remove(proposal_id);
+                    match request {
+                        PriceRequest::Tier {
+                            category,
+                            tier,
+                            currency,
+                            price,
+                        } => {
+                            match currency {
+                                Currency::Token => self.prices.insert((&category, &tier), &price),
+                                Currency::Native => {
+                                    self.native_prices.insert((&category, &tier), &price)
+                                }
+                            };
+                            self.env().emit_event(PriceChanged {
+                                category,
+                                tier,
+                                currency,
+                                price,
+                            });
+                        }
+                        PriceRequest::Discount { period, percent } => {
+                            self.period_discounts.insert(&period, &percent);
+                            self.env().emit_event(DiscountChanged { period, percent });
+                        }
+                    }
+                    Ok(())
+                }
+            }
+        }
+
+        /// Claim the institutional or school status for the caller.
+        /// The new price schedule applies from the next renewal once a council member verifies it.
+        #[ink(message)]
+        pub fn request_category(&mut self, institutional: bool, school: bool) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.members.get(caller).ok_or(Error::UserNotFound)?;
+            let category = requested_category(institutional, school)?
+                .ok_or(Error::InvalidCategory)?;
+            self.category_requests.insert(caller, &category);
+            Ok(())
+        }
+
+        /// Council members approve or reject the institutional or school status
+        /// claimed by another member
+        #[ink(message)]
+        pub fn verify_category(&mut self, account: Address, approve: bool) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let council_member = self.active_member(caller)?;
+            if !council_member.council {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            // A council member cannot verify their own claim
+            if account == caller {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            let category = self
+                .category_requests
+                .get(account)
+                .ok_or(Error::InvalidCategory)?;
+            self.category_requests.remove(account);
+            if !approve {
+                return Ok(());
+            }
+            let mut member = self.members.get(account).ok_or(Error::UserNotFound)?;
+            member.institutional = category == PricingCategory::Institutional;
+            member.school = category == PricingCategory::School;
+            self.members.insert(account, &member);
+            self.sync_voter(account)?;
+            self.env().emit_event(CategoryVerified {
+                who: Some(account),
+                category,
+            });
+            Ok(())
+        }
+
+        /// Get the institutional or school status an account is waiting to be verified for
+        #[ink(message)]
+        pub fn get_category_request(&self, account: Address) -> Option<PricingCategory> {
+            self.category_requests.get(account)
+        }
+
+        /// Council members can request a change of the grace windows and suspension
+        /// rules. The periods must stay within the `MIN_*` and `MAX_*` bounds, and the
+        /// rules only apply once the proposal is executed.
+        #[ink(message)]
+        pub fn request_subscription_rules_change(
+            &mut self,
+            rules: SubscriptionRules,
+            description: Vec<u8>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let member = self.active_member(caller)?;
+            if !member.council {
+                return Err(Error::NotAnAuthorisedUser);
+            }
+            if !rules.is_valid() {
+                return Err(Error::InvalidSubscriptionRules);
+            }
+            let call_builder = self.governance.call_mut();
+            let proposal_id = call_builder
+                .create_proposal(
+                    description,
+                    ProposalType::SubscriptionRules,
+                    None,
+                    U256::zero(),
+                    caller,
+                )
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            self.subscription_rules_requests.insert(proposal_id, &rules);
+            Ok(())
+        }
+
+        /// Get the rules currently driving the subscription lifecycle
         #[ink(message)]
-        pub fn execute_proposal(&mut self, proposal_id: u32) -> Result<(), Error> {
+        pub fn get_subscription_rules(&self) -> SubscriptionRules {
+            self.subscription_rules.clone()
+        }
+
+        /// Get the lifecycle state of an account's subscription at the current block
+        #[ink(message)]
+        pub fn get_subscription_status(&self, account: Address) -> Option<SubscriptionStatus> {
+            let current_block = self.env().block_number();
+            self.members
+                .get(account)
+                .map(|member| member.status_at(current_block, &self.subscription_rules))
+        }
+
+        /// Get the current price of a tier for a pricing category and a billing period,
+        /// discount included
+        #[ink(message)]
+        pub fn get_subscription_price(
+            &self,
+            category: PricingCategory,
+            tier: SubscriptionType,
+            period: BillingPeriod,
+        ) -> U256 {
+            self.period_price(&category, &tier, &period)
+        }
+
+        /// Get the current native currency price of a tier for a pricing category and
+        /// a billing period, discount included. `None` if it can only be paid in tokens.
+        #[ink(message)]
+        pub fn get_native_subscription_price(
+            &self,
+            category: PricingCategory,
+            tier: SubscriptionType,
+            period: BillingPeriod,
+        ) -> Option<U256> {
+            self.native_period_price(&category, &tier, &period)
+        }
+
+        /// Get the discount, in percent, granted on a billing period
+        #[ink(message)]
+        pub fn get_period_discount(&self, period: BillingPeriod) -> u8 {
+            self.period_discounts.get(&period).unwrap_or_default()
+        }
+
+        /// Get the number of members in the registry
+        #[ink(message)]
+        pub fn member_count(&self) -> u32 {
+            self.member_count
+        }
+
+        /// List the members of the registry, `limit` is capped to `MAX_PAGE_SIZE`
+        #[ink(message)]
+        pub fn list_members(&self, offset: u32, limit: u32) -> Vec<Address> {
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(self.member_count);
+            (offset..end)
+                .filter_map(|position| self.member_list.get(position))
+                .collect()
+        }
+
+        /// List the members subscribed to a tier, `limit` is capped to `MAX_PAGE_SIZE`
+        #[ink(message)]
+        pub fn list_members_by_tier(
+            &self,
+            tier: SubscriptionType,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<Address> {
+            self.list_group(MemberGroup::Tier(tier), offset, limit)
+        }
+
+        /// List the members holding a role, `limit` is capped to `MAX_PAGE_SIZE`
+        #[ink(message)]
+        pub fn list_members_by_role(&self, role: Roles, offset: u32, limit: u32) -> Vec<Address> {
+            self.list_group(MemberGroup::Role(role), offset, limit)
+        }
+
+        /// List the members whose subscription is in a lifecycle state at the current block.
+        /// The state changes with time, so it cannot be indexed: `offset` and `limit`
+        /// select a page of the registry, as in `list_members`, which is then filtered.
+        #[ink(message)]
+        pub fn list_members_by_status(
+            &self,
+            status: SubscriptionStatus,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<Address> {
+            let current_block = self.env().block_number();
+            self.list_members(offset, limit)
+                .into_iter()
+                .filter(|account| {
+                    self.members.get(account).is_some_and(|member| {
+                        member.status_at(current_block, &self.subscription_rules) == status
+                    })
+                })
+                .collect()
+        }
+
+        /// Get the number of members subscribed to a tier
+        #[ink(message)]
+        pub fn count_members_by_tier(&self, tier: SubscriptionType) -> u32 {
+            self.group_counts.get(MemberGroup::Tier(tier)).unwrap_or_default()
+        }
+
+        /// Get the number of members holding a role
+        #[ink(message)]
+        pub fn count_members_by_role(&self, role: Roles) -> u32 {
+            self.group_counts.get(MemberGroup::Role(role)).unwrap_or_default()
+        }
+
+        /// Get the members currently holding a council seat
+        #[ink(message)]
+        pub fn get_council(&self) -> Vec<Address> {
+            self.council_members.clone()
+        }
+
+        /// Get the block at which the current council term ends
+        #[ink(message)]
+        pub fn get_council_term_end(&self) -> BlockNumber {
+            self.council_term_end
+        }
+
+        /// Get the election collecting nominations for the next council term
+        #[ink(message)]
+        pub fn get_next_election(&self) -> Option<Election> {
+            let election_id = self.next_election?;
+            let call_builder = self.governance.call();
+            call_builder
+                .get_election(election_id)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .ok()
+        }
+
+        /// Get the mentees of a mentor
+        #[ink(message)]
+        pub fn get_mentees(&self, mentor: Address) -> Vec<Address> {
+            self.mentees.get(mentor).unwrap_or_default()
+        }
+
+        /// Get the mentor of a mentee
+        #[ink(message)]
+        pub fn get_mentor_of(&self, mentee: Address) -> Option<Address> {
+            self.mentor_of.get(mentee)
+        }
+
+        /// Get the number of mentees a mentor accepts
+        #[ink(message)]
+        pub fn get_mentor_capacity(&self, mentor: Address) -> u32 {
+            self.mentor_capacity.get(mentor).unwrap_or_default()
+        }
+
+        /// Get the token balance held by the DAO treasury
+        #[ink(message)]
+        pub fn treasury_balance(&self) -> U256 {
+            let dao_account = self.env().address();
+            let call_builder = self.erc20.call();
+            call_builder
+                .balance_of(dao_account)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+        }
+
+        /// Get the part of the treasury already owed and not paid out yet
+        #[ink(message)]
+        pub fn get_committed_funds(&self) -> U256 {
+            self.committed_funds
+        }
+
+        /// Get the part of the treasury free for new spending
+        #[ink(message)]
+        pub fn get_available_funds(&self) -> U256 {
+            self.treasury_balance().saturating_sub(self.committed_funds)
+        }
+
+        /// Get the native currency balance held by the DAO treasury
+        #[ink(message)]
+        pub fn native_treasury_balance(&self) -> U256 {
+            self.env().balance()
+        }
+
+        /// Get the part of the native treasury already owed and not paid out yet
+        #[ink(message)]
+        pub fn get_committed_native_funds(&self) -> U256 {
+            self.committed_native_funds
+        }
+
+        /// Get the part of the native treasury free for new spending
+        #[ink(message)]
+        pub fn get_available_native_funds(&self) -> U256 {
+            self.native_treasury_balance()
+                .saturating_sub(self.committed_native_funds)
+        }
+
+        /// Get a stream created by a Spending proposal
+        #[ink(message)]
+        pub fn get_stream(&self, stream_id: u32) -> Option<Stream> {
+            self.streams.get(stream_id)
+        }
+
+        /// Get the amount a stream's beneficiary can withdraw now
+        #[ink(message)]
+        pub fn get_withdrawable(&self, stream_id: u32) -> U256 {
+            self.streams
+                .get(stream_id)
+                .map(|stream| {
+                    stream
+                        .vested_amount(self.env().block_number())
+                        .saturating_sub(stream.withdrawn)
+                })
+                .unwrap_or_default()
+        }
+
+        /// Get the payment made for a Spending proposal
+        #[ink(message)]
+        pub fn get_spending(&self, proposal_id: u32) -> Option<SpendingRecord> {
+            self.spending_ledger.get(proposal_id)
+        }
+
+        /// Get the payments made by the treasury, oldest first.
+        /// `limit` is capped to `MAX_PAGE_SIZE`.
+        #[ink(message)]
+        pub fn get_spending_history(&self, offset: u32, limit: u32) -> Vec<SpendingRecord> {
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(self.spending_count);
+            (offset..end)
+                .filter_map(|index| self.spending_history.get(index))
+                .filter_map(|proposal_id| self.spending_ledger.get(proposal_id))
+                .collect()
+        }
+
+        /// Get the rewards a mentor can claim
+        #[ink(message)]
+        pub fn get_mentor_rewards(&self, mentor: Address) -> U256 {
+            self.mentor_rewards.get(mentor).unwrap_or_default()
+        }
+
+        /// Get the compensation currently paid to mentors
+        #[ink(message)]
+        pub fn get_mentor_rewards_config(&self) -> MentorRewardsConfig {
+            self.mentor_rewards_config.clone()
+        }
+
+        /// Get the voting power granted to each tier and role
+        #[ink(message)]
+        pub fn get_voting_weights(&self) -> VotingWeights {
+            self.voting_weights.clone()
+        }
+
+        /// Get the part of the current epoch budget that is still available
+        #[ink(message)]
+        pub fn get_epoch_budget_left(&self) -> U256 {
+            let (_, budget_left) = self.current_epoch();
+            budget_left
+        }
+
+        /// Get a mentoring session
+        #[ink(message)]
+        pub fn get_session(&self, session_id: u32) -> Option<Session> {
+            self.sessions.get(session_id)
+        }
+
+        /// Get the session history of a mentor, `limit` is capped to `MAX_PAGE_SIZE`
+        #[ink(message)]
+        pub fn get_mentor_sessions(&self, mentor: Address, offset: u32, limit: u32) -> Vec<Session> {
+            let logged = self.mentor_session_counts.get(mentor).unwrap_or_default();
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(logged);
+            (offset..end)
+                .filter_map(|index| self.mentor_sessions.get((mentor, index)))
+                .filter_map(|session_id| self.sessions.get(session_id))
+                .collect()
+        }
+
+        /// Get user information
+        #[ink(message)]
+        pub fn get_user(&self, account: Address) -> Option<User> {
+            self.members.get(account)
+        }
+
+        /// Get a seat pool
+        #[ink(message)]
+        pub fn get_seat_pool(&self, pool_id: u32) -> Option<SeatPool> {
+            self.seat_pools.get(pool_id)
+        }
+
+        /// Get the number of seats of a pool currently assigned
+        #[ink(message)]
+        pub fn get_used_seats(&self, pool_id: u32) -> Result<u32, Error> {
+            let pool = self.seat_pools.get(pool_id).ok_or(Error::SeatPoolNotFound)?;
+            Ok(pool.holders.len() as u32)
+        }
+
+        /// Get the number of seats of a pool still available
+        #[ink(message)]
+        pub fn get_remaining_seats(&self, pool_id: u32) -> Result<u32, Error> {
+            let pool = self.seat_pools.get(pool_id).ok_or(Error::SeatPoolNotFound)?;
+            Ok(pool.seats.saturating_sub(pool.holders.len() as u32))
+        }
+
+        /// Get the pool holding the seat of a sponsored member
+        #[ink(message)]
+        pub fn get_seat_pool_of(&self, account: Address) -> Option<u32> {
+            self.seat_holders.get(account)
+        }
+
+        /// Get the archived record of a member who cancelled their subscription
+        #[ink(message)]
+        pub fn get_archived_user(&self, account: Address) -> Option<User> {
+            self.archived_members.get(account)
+        }
+
+        /// Debug method to get the caller's address as seen by the contract
+        #[ink(message)]
+        pub fn get_caller_address(&self) -> Address {
+            self.env().caller()
+        }
+
+        /// Debug method to get user by caller (uses env().caller())
+        #[ink(message)]
+        pub fn get_my_subscription(&self) -> Option<User> {
             let caller = self.env().caller();
+            self.members.get(caller)
+        }
+
+        /// Helper function to approve the DAO contract to spend caller's tokens
+        /// This allows users to approve token spending in one transaction
+        #[ink(message)]
+        pub fn approve_dao(&mut self, amount: U256) -> Result<(), Error> {
             let dao_account = self.env().address();
-            let member = self.members.get(caller).ok_or(Error::UserNotFound)?;
-            if member.council != true && member.mentor != true {
-                return Err(Error::NotAnAuthorisedUser);
+
+            // Call the ERC20 approve function
+            let call_builder = self.erc20.call_mut();
+            let _approve_result = call_builder
+                .approve(dao_account, amount)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke();
+
+            Ok(())
+        }
+
+        /// Current monthly price of a tier in a pricing category, as set by governance
+        fn subscription_amount(&self, category: &PricingCategory, tier: &SubscriptionType) -> U256 {
+            self.prices.get((category, tier)).unwrap_or_default()
+        }
+
+        /// Price of one billing period of a tier, after the period discount
+        fn period_price(
+            &self,
+            category: &PricingCategory,
+            tier: &SubscriptionType,
+            period: &BillingPeriod,
+        ) -> U256 {
+            self.discounted_price(self.subscription_amount(category, tier), period)
+        }
+
+        /// Price of one billing period of a tier in native currency, after the period
+        /// discount. `None` while governance has not set a native price for the tier.
+        fn native_period_price(
+            &self,
+            category: &PricingCategory,
+            tier: &SubscriptionType,
+            period: &BillingPeriod,
+        ) -> Option<U256> {
+            self.native_prices
+                .get((category, tier))
+                .map(|price| self.discounted_price(price, period))
+        }
+
+        /// Turns a monthly price into the price of `period`, after its discount
+        fn discounted_price(&self, monthly: U256, period: &BillingPeriod) -> U256 {
+            let discount = self.period_discounts.get(period).unwrap_or_default().min(100);
+            monthly
+                .saturating_mul(U256::from(period.months()))
+                .saturating_mul(U256::from(100 - discount))
+                / U256::from(100)
+        }
+
+        /// Collects the price of one billing period of a tier from `from`.
+        /// A value sent with the call pays in native currency and must match the native
+        /// price exactly, otherwise the token price is transferred with `collect_payment`.
+        fn take_payment(
+            &mut self,
+            from: Address,
+            category: &PricingCategory,
+            tier: &SubscriptionType,
+            period: &BillingPeriod,
+        ) -> Result<(U256, Currency), Error> {
+            let paid = self.env().transferred_value();
+            if paid == U256::zero() {
+                let amount = self.period_price(category, tier, period);
+                self.collect_payment(from, amount)?;
+                return Ok((amount, Currency::Token));
+            }
+            let amount = self
+                .native_period_price(category, tier, period)
+                .ok_or(Error::NativePriceNotSet)?;
+            if paid != amount {
+                return Err(Error::InvalidSubscriptionAmount);
+            }
+            Ok((amount, Currency::Native))
+        }
+
+        /// Moves a member to the requested tier for the rest of their current period.
+        /// The unused value of the old tier is credited against the pro-rated price
+        /// of the new one: the member pays the difference or is refunded the surplus.
+        fn apply_tier_change(&mut self, request: TierRequest) -> Result<(), Error> {
+            let member = self.members.get(request.member).ok_or(Error::UserNotFound)?;
+            let (credit, cost) =
+                self.tier_change_amounts(&member, &request.tier, self.env().block_number());
+
+            match member.subscription.currency {
+                Currency::Token if cost > credit => {
+                    self.collect_payment(request.member, cost.saturating_sub(credit))?;
+                }
+                Currency::Token => {
+                    self.send_payment(request.member, credit.saturating_sub(cost))?;
+                }
+                // Native currency can only be collected in a payable call: the member
+                // moves to the new tier once they pay the difference
+                Currency::Native if cost > credit => {
+                    self.owed_tier_changes.insert(request.member, &request.tier);
+                    return Ok(());
+                }
+                Currency::Native => {
+                    self.send_native(request.member, credit.saturating_sub(cost))?;
+                }
+            }
+            self.set_tier(request.member, member, request.tier, cost)
+        }
+
+        /// Unused value of the member's current tier, and price of `tier` for the
+        /// blocks left in their current period
+        fn tier_change_amounts(
+            &self,
+            member: &User,
+            tier: &SubscriptionType,
+            now: BlockNumber,
+        ) -> (U256, U256) {
+            let credit = member.subscription.unused_amount(now);
+            let cost = prorated_price(
+                self.period_price(&member.category(), tier, &member.subscription.period),
+                member.subscription.end.saturating_sub(now),
+                member.subscription.period.blocks(),
+            );
+            (credit, cost)
+        }
+
+        /// Records the new tier of a member, paid `cost` for the rest of their period
+        fn set_tier(
+            &mut self,
+            account: Address,
+            mut member: User,
+            tier: SubscriptionType,
+            cost: U256,
+        ) -> Result<(), Error> {
+            let current_block = self.env().block_number();
+            member.subscription.subscription_type = tier;
+            member.subscription.amount = cost;
+            member.subscription.start = current_block.min(member.subscription.end);
+            self.members.insert(account, &member);
+            self.refresh_groups(account);
+            self.sync_voter(account)
+        }
+
+        /// Transfers `amount` tokens from `from` to the DAO account.
+        /// The caller must have approved the DAO beforehand, a failed transfer
+        /// is returned as `PaymentFailed` so the whole call is reverted.
+        fn collect_payment(&mut self, from: Address, amount: U256) -> Result<(), Error> {
+            if amount == U256::zero() {
+                return Ok(());
+            }
+            let dao_account = self.env().address();
+            let call_builder = self.erc20.call_mut();
+            call_builder
+                .transfer_from(from, dao_account, amount)
+                .ref_time_limit(100000000000)
+                .proof_size_limit(10000000)
+                .storage_deposit_limit(500000000000u128.into())
+                .invoke()
+                .map_err(Error::PaymentFailed)
+        }
+
+        /// Transfers `amount` tokens from the DAO balance to `to`.
+        /// A failed transfer is returned as `PaymentFailed` so the whole call is reverted.
+        fn send_payment(&mut self, to: Address, amount: U256) -> Result<(), Error> {
+            if amount == U256::zero() {
+                return Ok(());
+            }
+            let call_builder = self.erc20.call_mut();
+            call_builder
+                .transfer(to, amount)
+                .ref_time_limit(100000000000)
+                .proof_size_limit(10000000)
+                .storage_deposit_limit(500000000000u128.into())
+                .invoke()
+                .map_err(Error::PaymentFailed)
+        }
+
+        /// Transfers `amount` of native currency from the DAO balance to `to`
+        fn send_native(&mut self, to: Address, amount: U256) -> Result<(), Error> {
+            if amount == U256::zero() {
+                return Ok(());
+            }
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| Error::NativeTransferFailed)
+        }
+
+        /// Pays `amount` from the DAO balance to `to` in the given currency
+        fn send_funds(&mut self, to: Address, amount: U256, currency: &Currency) -> Result<(), Error> {
+            match currency {
+                Currency::Token => self.send_payment(to, amount),
+                Currency::Native => self.send_native(to, amount),
+            }
+        }
+
+        /// Part of the treasury held in `currency` free for new spending
+        fn available_funds(&self, currency: &Currency) -> U256 {
+            match currency {
+                Currency::Token => self.get_available_funds(),
+                Currency::Native => self.get_available_native_funds(),
+            }
+        }
+
+        /// Sets `amount` aside for a payment owed later
+        fn commit_funds(&mut self, currency: &Currency, amount: U256) {
+            match currency {
+                Currency::Token => {
+                    self.committed_funds = self.committed_funds.saturating_add(amount)
+                }
+                Currency::Native => {
+                    self.committed_native_funds = self.committed_native_funds.saturating_add(amount)
+                }
+            }
+        }
+
+        /// Releases `amount` previously set aside with `commit_funds`
+        fn release_funds(&mut self, currency: &Currency, amount: U256) {
+            match currency {
+                Currency::Token => {
+                    self.committed_funds = self.committed_funds.saturating_sub(amount)
+                }
+                Currency::Native => {
+                    self.committed_native_funds = self.committed_native_funds.saturating_sub(amount)
+                }
+            }
+        }
+
+        /// Registers `account` as a voter in governance, or updates its voting power
+        /// if it already is one, after it joined, renewed or changed tier.
+        /// Suspended and cancelled members keep no voting power, so they do not
+        /// weigh on the quorum.
+        fn sync_voter(&mut self, account: Address) -> Result<(), Error> {
+            let voting_power = match self.active_member(account) {
+                Ok(member) => self.voting_weights.voting_power(&member),
+                Err(Error::UserNotFound) => return Err(Error::UserNotFound),
+                Err(_) => U256::zero(),
+            };
+            let call_builder = self.governance.call_mut();
+            let registered = call_builder
+                .get_voter(account)
+                .ref_time_limit(100000000000)
+                .proof_size_limit(10000000)
+                .storage_deposit_limit(500000000000u128.into())
+                .invoke()
+                .is_some();
+            if registered {
+                call_builder
+                    .update_voter(account, voting_power)
+                    .ref_time_limit(100000000000)
+                    .proof_size_limit(10000000)
+                    .storage_deposit_limit(500000000000u128.into())
+                    .invoke()
+                    .map_err(Error::GovernanceFailed)
+            } else {
+                call_builder
+                    .register_voter(account, voting_power)
+                    .ref_time_limit(100000000000)
+                    .proof_size_limit(10000000)
+                    .storage_deposit_limit(500000000000u128.into())
+                    .invoke()
+                    .map_err(Error::GovernanceFailed)
+            }
+        }
+
+        /// Removes a member who left the DAO from the governance voters.
+        /// Members who joined before voters were registered have nothing to remove.
+        fn remove_voter(&mut self, account: Address) -> Result<(), Error> {
+            let call_builder = self.governance.call_mut();
+            match call_builder
+                .remove_voter(account)
+                .ref_time_limit(100000000000)
+                .proof_size_limit(10000000)
+                .storage_deposit_limit(500000000000u128.into())
+                .invoke()
+            {
+                Ok(()) | Err(governance::Error::VoterNotFound) => Ok(()),
+                Err(error) => Err(Error::GovernanceFailed(error)),
+            }
+        }
+
+        /// Returns the election for the next council term, opening it if needed.
+        /// It runs until the end of the current term, and at least `ELECTION_PERIOD`.
+        fn ensure_next_election(&mut self) -> Result<u32, Error> {
+            if let Some(election_id) = self.next_election {
+                return Ok(election_id);
+            }
+            let end = self
+                .council_term_end
+                .max(self.env().block_number().saturating_add(ELECTION_PERIOD));
+            let call_builder = self.governance.call_mut();
+            let election_id = call_builder
+                .create_election(COUNCIL_SEATS, end)
+                .ref_time_limit(1000000)
+                .proof_size_limit(1000000)
+                .storage_deposit_limit(1000000.into())
+                .invoke()
+                .map_err(Error::GovernanceFailed)?;
+            self.next_election = Some(election_id);
+            Ok(election_id)
+        }
+
+        /// Adds `account` at the end of the member registry if it is not there yet,
+        /// and to the index of its groups
+        fn index_member(&mut self, account: Address) {
+            self.refresh_groups(account);
+            if self.member_positions.contains(account) {
+                return;
+            }
+            self.member_list.insert(self.member_count, &account);
+            self.member_positions.insert(account, &self.member_count);
+            self.member_count += 1;
+        }
+
+        /// Removes `account` from the member registry by moving the last member
+        /// into its position
+        fn unindex_member(&mut self, account: Address) {
+            let Some(position) = self.member_positions.get(account) else {
+                return;
+            };
+            let last = self.member_count.saturating_sub(1);
+            if position != last {
+                if let Some(moved) = self.member_list.get(last) {
+                    self.member_list.insert(position, &moved);
+                    self.member_positions.insert(moved, &position);
+                }
+            }
+            self.member_list.remove(last);
+            self.member_positions.remove(account);
+            self.member_count = last;
+            self.refresh_groups(account);
+        }
+
+        /// Puts `account` in the index of the groups its stored record belongs to,
+        /// and takes it out of the others. Called whenever a tier or role changes.
+        fn refresh_groups(&mut self, account: Address) {
+            let member = self.members.get(account);
+            for group in MemberGroup::ALL {
+                let belongs = member.as_ref().is_some_and(|member| group.contains(member));
+                let indexed = self.group_positions.contains((&group, &account));
+                if belongs && !indexed {
+                    let count = self.group_counts.get(&group).unwrap_or_default();
+                    self.group_members.insert((&group, &count), &account);
+                    self.group_positions.insert((&group, &account), &count);
+                    self.group_counts.insert(&group, &(count + 1));
+                } else if !belongs && indexed {
+                    self.remove_from_group(&group, account);
+                }
             }
-            // Execute the proposal
-            let call_builder = self.governance.call_mut();
-            let proposal = call_builder
-                .get_proposal(proposal_id)
-                .ref_time_limit(1000000)
-                .proof_size_limit(1000000)
-                .storage_deposit_limit(1000000.into())
-                .invoke()
-                .map_err(|_| Error::ProposalNotFound)?;
+        }
 
-            let _ = call_builder
-                .update_proposal_status(proposal_id, false)
-                .ref_time_limit(1000000)
-                .proof_size_limit(1000000)
-                .storage_deposit_limit(1000000.into())
-                .invoke();
-            let proposal_type = proposal.basic_infos.proposal_type;
-            let owner_id = proposal.owner;
+        /// Removes `account` from the index of a group by moving the last member
+        /// of the group into its position
+        fn remove_from_group(&mut self, group: &MemberGroup, account: Address) {
+            let Some(position) = self.group_positions.get((group, &account)) else {
+                return;
+            };
+            let last = self.group_counts.get(group).unwrap_or_default().saturating_sub(1);
+            if position != last {
+                if let Some(moved) = self.group_members.get((group, &last)) {
+                    self.group_members.insert((group, &position), &moved);
+                    self.group_positions.insert((group, &moved), &position);
+                }
+            }
+            self.group_members.remove((group, &last));
+            self.group_positions.remove((group, &account));
+            self.group_counts.insert(group, &last);
+        }
 
-            match proposal_type {
-                ProposalType::Spending => {
-                    let transaction = proposal
-                        .transaction
-                        .clone()
-                        .ok_or(Error::InvalidSubscription)?;
-                    let beneficiary = transaction.beneficiary;
-                    let amount = transaction.amount;
-                    // Transfer the amount to the beneficiary
-                    let call_builder = self.erc20.call_mut();
-                    let _transfer_result = call_builder
-                        .transfer_from(dao_account, beneficiary, amount.into())
+        /// Page of the index of a group, `limit` is capped to `MAX_PAGE_SIZE`
+        fn list_group(&self, group: MemberGroup, offset: u32, limit: u32) -> Vec<Address> {
+            let count = self.group_counts.get(&group).unwrap_or_default();
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+            (offset..end)
+                .filter_map(|position| self.group_members.get((&group, &position)))
+                .collect()
+        }
+
+        /// Drops the pending change carried by a cancelled proposal
+        fn discard_requests(&mut self, proposal_id: u32) {
+            self.tier_requests.remove(proposal_id);
+            self.price_requests.remove(proposal_id);
+            self.spending_currencies.remove(proposal_id);
+            self.stream_requests.remove(proposal_id);
+            self.stream_cancellations.remove(proposal_id);
+            self.mentor_rewards_requests.remove(proposal_id);
+            self.voting_weights_requests.remove(proposal_id);
+            self.voting_mode_requests.remove(proposal_id);
+            self.voting_rules_requests.remove(proposal_id);
+            self.subscription_rules_requests.remove(proposal_id);
+        }
+
+        /// Frees the seat held by `account`, if any, and returns the subscription the
+        /// member had before the seat was assigned
+        fn release_seat(&mut self, account: Address) -> Option<Subscription> {
+            let pool_id = self.seat_holders.get(account)?;
+            if let Some(mut pool) = self.seat_pools.get(pool_id) {
+                pool.holders.retain(|holder| *holder != account);
+                self.seat_pools.insert(pool_id, &pool);
+            }
+            self.seat_holders.remove(account);
+            let previous = self.seat_previous.get(account);
+            self.seat_previous.remove(account);
+            previous
+        }
+
+        /// Takes the seat of a pool back from `account` and restores the subscription
+        /// the member had before it, or ends the seat subscription at the current block
+        fn end_seat(&mut self, pool_id: u32, account: Address) -> Result<(), Error> {
+            let previous = self.release_seat(account);
+            if let Some(mut member) = self.members.get(account) {
+                match previous {
+                    Some(subscription) => member.subscription = subscription,
+                    None => {
+                        member.subscription.end =
+                            member.subscription.end.min(self.env().block_number())
+                    }
+                }
+                self.members.insert(account, &member);
+                self.refresh_groups(account);
+                self.sync_voter(account)?;
+            }
+            self.env().emit_event(SeatRevoked {
+                pool_id,
+                who: Some(account),
+            });
+            Ok(())
+        }
+
+        /// Pays an approved Spending proposal from the treasury's own balance,
+        /// once, and records it in the spending ledger. Streamed proposals only
+        /// commit the amount, the beneficiary withdraws it as it vests.
+        fn execute_spending(
+            &mut self,
+            proposal_id: u32,
+            beneficiary: Address,
+            amount: U256,
+        ) -> Result<(), Error> {
+            if self.spending_ledger.contains(proposal_id) {
+                return Err(Error::ProposalAlreadyExecuted);
+            }
+            let currency = self.spending_currencies.get(proposal_id).unwrap_or_default();
+            self.spending_currencies.remove(proposal_id);
+            if amount > self.available_funds(&currency) {
+                return Err(Error::InsufficientFunds);
+            }
+            match self.stream_requests.get(proposal_id) {
+                Some(schedule) => {
+                    self.stream_requests.remove(proposal_id);
+                    let stream = Stream {
+                        beneficiary,
+                        amount,
+                        withdrawn: U256::zero(),
+                        currency: currency.clone(),
+                        schedule,
+                        cancelled_at: None,
+                    };
+                    self.streams.insert(proposal_id, &stream);
+                    self.commit_funds(&currency, amount);
+                    self.env().emit_event(StreamCreated {
+                        stream_id: proposal_id,
+                        beneficiary: Some(beneficiary),
+                        amount,
+                        end: stream.schedule.end,
+                    });
+                }
+                None => self.send_funds(beneficiary, amount, &currency)?,
+            }
+
+            let record = SpendingRecord {
+                proposal_id,
+                beneficiary,
+                amount,
+                currency,
+                executed_at: self.env().block_number(),
+            };
+            self.spending_ledger.insert(proposal_id, &record);
+            self.spending_history.insert(self.spending_count, &proposal_id);
+            self.spending_count += 1;
+            self.env().emit_event(SpendingExecuted {
+                proposal_id,
+                beneficiary: Some(beneficiary),
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Replaces the code of `target`. The DAO upgrades itself, the governance and
+        /// token contracts are asked to, and migrated right away as their new code is
+        /// used by the next call. The DAO's own `migrate` runs in a later call.
+        fn execute_upgrade(&mut self, target: UpgradeTarget, code_hash: H256) -> Result<(), Error> {
+            match target {
+                UpgradeTarget::Dao => self
+                    .env()
+                    .set_code_hash(&code_hash)
+                    .map_err(|_| Error::UpgradeFailed)?,
+                UpgradeTarget::Governance => {
+                    let call_builder = self.governance.call_mut();
+                    call_builder
+                        .set_code(code_hash)
                         .ref_time_limit(1000000)
                         .proof_size_limit(1000000)
                         .storage_deposit_limit(1000000.into())
-                        .invoke();
-                    Ok(())
-                }
-                ProposalType::NewCouncilvoter => {
-                    // Change user's role to council
-                    let mut owner = self.members.get(owner_id).ok_or(Error::UserNotFound)?;
-                    owner.council = true;
-                    self.members.insert(owner_id, &owner);
-                    Ok(())
+                        .invoke()
+                        .map_err(Error::GovernanceFailed)?;
+                    call_builder
+                        .migrate()
+                        .ref_time_limit(1000000)
+                        .proof_size_limit(1000000)
+                        .storage_deposit_limit(1000000.into())
+                        .invoke()
+                        .map_err(Error::GovernanceFailed)?;
                 }
-                ProposalType::NewMentor => {
-                    let mut owner = self.members.get(owner_id).ok_or(Error::UserNotFound)?;
-                    owner.mentor = true;
-                    self.members.insert(owner_id, &owner);
-                    Ok(())
+                UpgradeTarget::Token => {
+                    let call_builder = self.erc20.call_mut();
+                    call_builder
+                        .set_code(code_hash)
+                        .ref_time_limit(1000000)
+                        .proof_size_limit(1000000)
+                        .storage_deposit_limit(1000000.into())
+                        .invoke()
+                        .map_err(|_| Error::UpgradeFailed)?;
+                    call_builder
+                        .migrate()
+                        .ref_time_limit(1000000)
+                        .proof_size_limit(1000000)
+                        .storage_deposit_limit(1000000.into())
+                        .invoke()
+                        .map_err(|_| Error::UpgradeFailed)?;
                 }
             }
+            self.env().emit_event(Upgraded { target, code_hash });
+            Ok(())
         }
 
-        /// Get user information
-        #[ink(message)]
-        pub fn get_user(&self, account: Address) -> Option<User> {
-            self.members.get(account)
+        /// Stops the vesting of a stream at the current block and releases the
+        /// unvested part back to the treasury. The vested part stays withdrawable.
+        fn cancel_stream(&mut self, stream_id: u32) -> Result<(), Error> {
+            let mut stream = self.streams.get(stream_id).ok_or(Error::StreamNotFound)?;
+            if stream.cancelled_at.is_some() {
+                return Err(Error::StreamCancelled);
+            }
+            let current_block = self.env().block_number();
+            let released = stream.amount.saturating_sub(stream.vested_amount(current_block));
+            stream.cancelled_at = Some(current_block);
+            self.streams.insert(stream_id, &stream);
+            self.release_funds(&stream.currency, released);
+            self.env().emit_event(StreamCancelled {
+                stream_id,
+                released,
+            });
+            Ok(())
         }
 
-        /// Debug method to get the caller's address as seen by the contract
-        #[ink(message)]
-        pub fn get_caller_address(&self) -> Address {
-            self.env().caller()
+        /// Start block and budget left of the current epoch. Once the stored epoch
+        /// is over, the epoch running at the current block starts with a full budget.
+        fn current_epoch(&self) -> (BlockNumber, U256) {
+            let elapsed = self.env().block_number().saturating_sub(self.epoch_start);
+            let epoch_length = self.mentor_rewards_config.epoch_length;
+            if epoch_length == 0 || elapsed < epoch_length {
+                return (self.epoch_start, self.epoch_budget_left);
+            }
+            let start = self.epoch_start.saturating_add(elapsed - elapsed % epoch_length);
+            (start, self.mentor_rewards_config.epoch_budget)
         }
 
-        /// Debug method to get user by caller (uses env().caller())
-        #[ink(message)]
-        pub fn get_my_subscription(&self) -> Option<User> {
-            let caller = self.env().caller();
-            self.members.get(caller)
+        /// Starts a new epoch at the current block with the full configured budget
+        fn start_epoch(&mut self) {
+            self.epoch_start = self.env().block_number();
+            self.epoch_budget_left = self.mentor_rewards_config.epoch_budget;
         }
 
-        /// Helper function to approve the DAO contract to spend caller's tokens
-        /// This allows users to approve token spending in one transaction
-        #[ink(message)]
-        pub fn approve_dao(&mut self, amount: U256) -> Result<(), Error> {
-            let dao_account = self.env().address();
+        /// Accrues the session rate to `mentor`, capped to what is left of the
+        /// epoch budget, and returns the amount accrued
+        fn accrue_mentor_reward(&mut self, mentor: Address) -> U256 {
+            let (epoch_start, budget_left) = self.current_epoch();
+            let reward = self.mentor_rewards_config.session_rate.min(budget_left);
+            if reward == U256::zero() {
+                return reward;
+            }
+            self.epoch_start = epoch_start;
+            self.epoch_budget_left = budget_left.saturating_sub(reward);
+            let accrued = self.mentor_rewards.get(mentor).unwrap_or_default();
+            self.mentor_rewards.insert(mentor, &accrued.saturating_add(reward));
+            self.committed_funds = self.committed_funds.saturating_add(reward);
+            reward
+        }
 
-            // Call the ERC20 approve function
-            let call_builder = self.erc20.call_mut();
-            let _approve_result = call_builder
-                .approve(dao_account, amount)
-                .ref_time_limit(1000000)
-                .proof_size_limit(1000000)
-                .storage_deposit_limit(1000000.into())
-                .invoke();
+        /// Returns the member stored for `account` if they are an active member
+        /// holding the mentor role
+        fn active_mentor(&self, account: Address) -> Result<User, Error> {
+            let member = self.active_member(account)?;
+            if !member.mentor {
+                return Err(Error::NotAMentor);
+            }
+            Ok(member)
+        }
 
-            Ok(())
+        /// Returns the member stored for `account` as long as their subscription
+        /// is active or in its grace window. Suspended members keep their record
+        /// and roles but lose their privileges until they renew.
+        fn active_member(&self, account: Address) -> Result<User, Error> {
+            let member = self.members.get(account).ok_or(Error::UserNotFound)?;
+            match member.status_at(self.env().block_number(), &self.subscription_rules)
+            {
+                SubscriptionStatus::Active | SubscriptionStatus::Grace => Ok(member),
+                SubscriptionStatus::Suspended => Err(Error::SubscriptionExpired),
+                SubscriptionStatus::Cancelled => Err(Error::SubscriptionCancelled),
+            }
         }
     }
 
@@ -415,14 +3441,88 @@ mod dao {
         NotPremiumUser,
         NotAnAuthorisedUser,
         ProposalNotFound,
+        SubscriptionExpired,
+        SubscriptionCancelled,
+        InvalidDiscount,
+        InvalidCategory,
+        SeatPoolNotFound,
+        NoSeatAvailable,
+        SeatAlreadyAssigned,
+        SeatNotAssigned,
+        SeatPoolExpired,
+        SeatPoolNotExpired,
+        PaymentFailed(my_erc20::Error),
+        ProfileFieldTooLong,
+        RoleNotHeld,
+        GovernanceFailed(governance::Error),
+        NoElectionRunning,
+        CouncilTermNotEnded,
+        NotAMentor,
+        AlreadyMentored,
+        MentorshipRequestNotFound,
+        MentorAtCapacity,
+        NotYourMentee,
+        SessionNotFound,
+        SessionAlreadyConfirmed,
+        NothingToClaim,
+        ProposalAlreadyExecuted,
+        InvalidStream,
+        StreamNotFound,
+        StreamCancelled,
+        NativePriceNotSet,
+        NativeTransferFailed,
+        CurrencyMismatch,
+        UpgradeFailed,
+        InvalidVotingRules,
+        CouncilIsElected,
+        NothingToSettle,
+        InvalidSubscriptionRules,
+        InvalidRewardsConfig,
+        InvalidVotingWeights,
+        InvalidMentorCapacity,
     }
 
-    pub fn subscription_amount(subscription: SubscriptionType) -> U256 {
-        match subscription {
+    /// Prices used when the contract is deployed, governance can change them afterwards
+    pub fn default_subscription_amount(
+        category: PricingCategory,
+        subscription: SubscriptionType,
+    ) -> U256 {
+        let amount = match subscription {
             SubscriptionType::Free => U256::from(0),
             SubscriptionType::Basic => U256::from(1000), // Example amount for Basic
             SubscriptionType::Premium => U256::from(5000), // Example amount for Premium
             SubscriptionType::Other => U256::from(10000), // Example amount for Other
+        };
+        match category {
+            PricingCategory::Standard => amount,
+            PricingCategory::Institutional => amount.saturating_mul(U256::from(2)),
+            PricingCategory::School => amount / U256::from(2),
+        }
+    }
+
+    /// Price of the `remaining` blocks of a billing period of `period_blocks` blocks
+    fn prorated_price(
+        period_price: U256,
+        remaining: BlockNumber,
+        period_blocks: BlockNumber,
+    ) -> U256 {
+        period_price
+            .saturating_mul(U256::from(remaining))
+            .checked_div(U256::from(period_blocks))
+            .unwrap_or_default()
+    }
+
+    /// Turns the `institutional` and `school` flags into the category to verify.
+    /// A member can claim at most one of them.
+    fn requested_category(
+        institutional: bool,
+        school: bool,
+    ) -> Result<Option<PricingCategory>, Error> {
+        match (institutional, school) {
+            (true, true) => Err(Error::InvalidCategory),
+            (true, false) => Ok(Some(PricingCategory::Institutional)),
+            (false, true) => Ok(Some(PricingCategory::School)),
+            (false, false) => Ok(None),
         }
     }
     /// Unit tests in Rust are normally defined within such a `#[cfg(test)]`
@@ -433,26 +3533,725 @@ mod dao {
         /// Imports all the definitions from the outer scope so we can use them here.
         use super::*;
 
+        /// A DAO connected to token and governance contracts that are never called:
+        /// the tests only cover paths that fail or return before reaching them
+        fn dao() -> Dao {
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_caller(accounts.alice);
+            Dao::from_addresses(
+                Address::from([0x01; 20]),
+                Address::from([0x02; 20]),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            )
+        }
+
         #[ink::test]
-        fn test_new_subscription() {
-            let mut dao = Dao::new();
-            let subscription = Subscription {
-                amount: Some(1000),
-                start: 0,
-                end: 30,
-                active: true,
+        fn native_payment_without_a_native_price_is_rejected() {
+            let accounts = ink::env::test::default_accounts();
+            let mut dao = dao();
+            ink::env::test::set_caller(accounts.bob);
+            ink::env::test::set_value_transferred(U256::from(1000));
+
+            assert_eq!(
+                dao.new_subscription(
+                    b"bob".to_vec(),
+                    SubscriptionType::Basic,
+                    BillingPeriod::Monthly,
+                    false,
+                    false,
+                ),
+                Err(Error::NativePriceNotSet)
+            );
+            assert!(dao.get_user(accounts.bob).is_none());
+            assert_eq!(dao.member_count(), 0);
+        }
+
+        #[ink::test]
+        fn native_payment_must_match_the_price() {
+            let accounts = ink::env::test::default_accounts();
+            let mut dao = dao();
+            dao.native_prices.insert(
+                (&PricingCategory::Standard, &SubscriptionType::Basic),
+                &U256::from(1000),
+            );
+            ink::env::test::set_caller(accounts.bob);
+            ink::env::test::set_value_transferred(U256::from(999));
+
+            assert_eq!(
+                dao.new_subscription(
+                    b"bob".to_vec(),
+                    SubscriptionType::Basic,
+                    BillingPeriod::Monthly,
+                    false,
+                    false,
+                ),
+                Err(Error::InvalidSubscriptionAmount)
+            );
+            assert!(dao.get_user(accounts.bob).is_none());
+            assert_eq!(dao.count_members_by_tier(SubscriptionType::Basic), 0);
+        }
+
+        fn subscription(amount: u32, start: BlockNumber, end: BlockNumber) -> Subscription {
+            Subscription {
+                amount: U256::from(amount),
+                start,
+                end,
+                status: SubscriptionStatus::Active,
                 subscription_type: SubscriptionType::Basic,
+                period: BillingPeriod::Monthly,
+                currency: Currency::Token,
+            }
+        }
+
+        #[test]
+        fn unused_amount_is_refunded_pro_rata() {
+            let subscription = subscription(1000, 100, 200);
+            assert_eq!(subscription.unused_amount(100), U256::from(1000));
+            assert_eq!(subscription.unused_amount(150), U256::from(500));
+            assert_eq!(subscription.unused_amount(200), U256::zero());
+            assert_eq!(subscription.unused_amount(250), U256::zero());
+        }
+
+        #[ink::test]
+        fn tier_change_credits_the_unused_part_of_the_old_tier() {
+            let dao = dao();
+            let period = BillingPeriod::Monthly.blocks();
+            let member = User::new(b"bob".to_vec(), subscription(1000, 0, period), false, false);
+
+            let (credit, cost) =
+                dao.tier_change_amounts(&member, &SubscriptionType::Premium, period / 2);
+            assert_eq!(credit, U256::from(500));
+            assert_eq!(cost, U256::from(2500));
+        }
+
+        #[ink::test]
+        fn spending_is_executed_once() {
+            let accounts = ink::env::test::default_accounts();
+            let mut dao = dao();
+            dao.spending_ledger.insert(
+                7,
+                &SpendingRecord {
+                    proposal_id: 7,
+                    beneficiary: accounts.bob,
+                    amount: U256::from(100),
+                    currency: Currency::Token,
+                    executed_at: 0,
+                },
+            );
+
+            assert_eq!(
+                dao.execute_spending(7, accounts.bob, U256::from(100)),
+                Err(Error::ProposalAlreadyExecuted)
+            );
+        }
+
+        #[test]
+        fn stream_vests_linearly_after_the_cliff() {
+            let mut stream = Stream {
+                beneficiary: Address::from([0x03; 20]),
+                amount: U256::from(1000),
+                withdrawn: U256::zero(),
+                currency: Currency::Token,
+                schedule: StreamSchedule {
+                    start: 100,
+                    end: 200,
+                    cliff: Some(150),
+                },
+                cancelled_at: None,
+            };
+            assert_eq!(stream.vested_amount(100), U256::zero());
+            assert_eq!(stream.vested_amount(149), U256::zero());
+            assert_eq!(stream.vested_amount(150), U256::from(500));
+            assert_eq!(stream.vested_amount(175), U256::from(750));
+            assert_eq!(stream.vested_amount(300), U256::from(1000));
+
+            stream.cancelled_at = Some(160);
+            assert_eq!(stream.vested_amount(300), U256::from(600));
+        }
+
+        #[test]
+        fn prorated_price_covers_the_remaining_blocks() {
+            assert_eq!(prorated_price(U256::from(3000), 50, 100), U256::from(1500));
+            assert_eq!(prorated_price(U256::from(3000), 0, 100), U256::zero());
+            assert_eq!(prorated_price(U256::from(3000), 50, 0), U256::zero());
+        }
+
+        #[ink::test]
+        fn native_payment_matching_the_price_is_taken() {
+            let accounts = ink::env::test::default_accounts();
+            let mut dao = dao();
+            dao.native_prices.insert(
+                (&PricingCategory::Standard, &SubscriptionType::Basic),
+                &U256::from(1000),
+            );
+            ink::env::test::set_value_transferred(U256::from(1000));
+
+            assert_eq!(
+                dao.take_payment(
+                    accounts.bob,
+                    &PricingCategory::Standard,
+                    &SubscriptionType::Basic,
+                    &BillingPeriod::Monthly,
+                ),
+                Ok((U256::from(1000), Currency::Native))
+            );
+        }
+
+        #[test]
+        fn status_follows_the_grace_and_suspension_windows() {
+            let rules = SubscriptionRules::default();
+            let subscription = subscription(1000, 0, 100);
+            let grace_end = 100 + rules.grace_period;
+            let suspension_end = grace_end + rules.suspension_period;
+
+            assert_eq!(subscription.status_at(100, &rules), SubscriptionStatus::Active);
+            assert_eq!(subscription.status_at(101, &rules), SubscriptionStatus::Grace);
+            assert_eq!(subscription.status_at(grace_end, &rules), SubscriptionStatus::Grace);
+            assert_eq!(
+                subscription.status_at(grace_end + 1, &rules),
+                SubscriptionStatus::Suspended
+            );
+            assert_eq!(
+                subscription.status_at(suspension_end, &rules),
+                SubscriptionStatus::Suspended
+            );
+            assert_eq!(
+                subscription.status_at(suspension_end + 1, &rules),
+                SubscriptionStatus::Cancelled
+            );
+        }
+
+        #[test]
+        fn suspension_never_ends_when_its_period_is_zero() {
+            let rules = SubscriptionRules {
+                suspension_period: 0,
+                ..Default::default()
+            };
+            let subscription = subscription(1000, 0, 100);
+            assert_eq!(
+                subscription.status_at(BlockNumber::MAX, &rules),
+                SubscriptionStatus::Suspended
+            );
+        }
+
+        #[test]
+        fn cancelled_subscription_stays_cancelled() {
+            let rules = SubscriptionRules::default();
+            let mut subscription = subscription(1000, 0, 100);
+            subscription.status = SubscriptionStatus::Cancelled;
+            assert_eq!(subscription.status_at(50, &rules), SubscriptionStatus::Cancelled);
+        }
+
+        #[test]
+        fn verified_members_get_the_longer_grace_window() {
+            let rules = SubscriptionRules::default();
+            let verified_grace_end = 100 + rules.verified_grace_period;
+            let standard = User::new(b"bob".to_vec(), subscription(1000, 0, 100), false, false);
+            let school = User::new(b"school".to_vec(), subscription(1000, 0, 100), false, true);
+
+            assert_eq!(
+                standard.status_at(verified_grace_end, &rules),
+                SubscriptionStatus::Suspended
+            );
+            assert_eq!(school.status_at(verified_grace_end, &rules), SubscriptionStatus::Grace);
+            assert_eq!(
+                school.status_at(verified_grace_end + 1, &rules),
+                SubscriptionStatus::Suspended
+            );
+        }
+
+        #[test]
+        fn subscription_rules_stay_within_bounds() {
+            assert!(SubscriptionRules::default().is_valid());
+            let rules = |grace_period, suspension_period| SubscriptionRules {
+                grace_period,
+                suspension_period,
+                ..Default::default()
+            };
+            assert!(rules(MIN_GRACE_PERIOD, 0).is_valid());
+            assert!(rules(MAX_GRACE_PERIOD, MAX_SUSPENSION_PERIOD).is_valid());
+            assert!(rules(MIN_GRACE_PERIOD, MIN_SUSPENSION_PERIOD).is_valid());
+            assert!(!rules(MIN_GRACE_PERIOD - 1, 0).is_valid());
+            assert!(!rules(MAX_GRACE_PERIOD + 1, 0).is_valid());
+            assert!(!rules(MIN_GRACE_PERIOD, MIN_SUSPENSION_PERIOD - 1).is_valid());
+            assert!(!rules(MIN_GRACE_PERIOD, MAX_SUSPENSION_PERIOD + 1).is_valid());
+            assert!(!SubscriptionRules {
+                verified_grace_period: 0,
+                ..Default::default()
+            }
+            .is_valid());
+        }
+
+        /// Stores an active monthly Basic member, skipping the payment
+        fn add_member(dao: &mut Dao, account: Address, mentor: bool) {
+            let period = BillingPeriod::Monthly.blocks();
+            let mut member =
+                User::new(b"member".to_vec(), subscription(1000, 0, period), false, false);
+            member.mentor = mentor;
+            dao.members.insert(account, &member);
+        }
+
+        #[ink::test]
+        fn direct_tier_changes_must_move_up() {
+            let accounts = ink::env::test::default_accounts();
+            let mut dao = dao();
+            add_member(&mut dao, accounts.bob, false);
+            add_member(&mut dao, accounts.charlie, false);
+            let mut free = dao.members.get(accounts.charlie).unwrap();
+            free.subscription.subscription_type = SubscriptionType::Free;
+            dao.members.insert(accounts.charlie, &free);
+
+            ink::env::test::set_caller(accounts.bob);
+            assert_eq!(
+                dao.update_subscription(SubscriptionType::Basic),
+                Err(Error::InvalidSubscription)
+            );
+            assert_eq!(
+                dao.update_subscription(SubscriptionType::Free),
+                Err(Error::InvalidSubscription)
+            );
+            ink::env::test::set_caller(accounts.charlie);
+            assert_eq!(
+                dao.update_subscription(SubscriptionType::Premium),
+                Err(Error::InvalidSubscription)
+            );
+            assert_eq!(
+                dao.get_user(accounts.charlie).unwrap().subscription.subscription_type,
+                SubscriptionType::Free
+            );
+        }
+
+        #[ink::test]
+        fn registry_keeps_members_and_groups_indexed_on_removal() {
+            let mut dao = dao();
+            let accounts: Vec<Address> = (1..=4).map(|n| Address::from([n; 20])).collect();
+            for (n, account) in accounts.iter().enumerate() {
+                add_member(&mut dao, *account, n % 2 == 0);
+                dao.index_member(*account);
+            }
+            assert_eq!(dao.list_members(0, 10), accounts);
+            assert_eq!(dao.count_members_by_tier(SubscriptionType::Basic), 4);
+            assert_eq!(dao.count_members_by_role(Roles::Mentor), 2);
+
+            // removing the first member moves the last one into its position
+            dao.members.remove(accounts[0]);
+            dao.unindex_member(accounts[0]);
+            assert_eq!(dao.list_members(0, 10), vec![accounts[3], accounts[1], accounts[2]]);
+            assert_eq!(dao.list_members_by_role(Roles::Mentor, 0, 10), vec![accounts[2]]);
+
+            // then a member in the middle, then the last one
+            dao.members.remove(accounts[1]);
+            dao.unindex_member(accounts[1]);
+            assert_eq!(dao.list_members(0, 10), vec![accounts[3], accounts[2]]);
+            dao.members.remove(accounts[2]);
+            dao.unindex_member(accounts[2]);
+            assert_eq!(dao.list_members(0, 10), vec![accounts[3]]);
+            assert_eq!(dao.member_count(), 1);
+            assert_eq!(
+                dao.list_members_by_tier(SubscriptionType::Basic, 0, 10),
+                vec![accounts[3]]
+            );
+            assert_eq!(dao.count_members_by_role(Roles::Mentor), 0);
+
+            // removing a member twice leaves the registry untouched
+            dao.unindex_member(accounts[2]);
+            assert_eq!(dao.member_count(), 1);
+        }
+
+        #[ink::test]
+        fn groups_follow_tier_and_role_changes() {
+            let mut dao = dao();
+            let first = Address::from([0x01; 20]);
+            let second = Address::from([0x02; 20]);
+            add_member(&mut dao, first, false);
+            add_member(&mut dao, second, false);
+            dao.index_member(first);
+            dao.index_member(second);
+
+            let mut member = dao.members.get(first).unwrap();
+            member.subscription.subscription_type = SubscriptionType::Premium;
+            member.mentor = true;
+            dao.members.insert(first, &member);
+            dao.refresh_groups(first);
+
+            assert_eq!(dao.list_members_by_tier(SubscriptionType::Basic, 0, 10), vec![second]);
+            assert_eq!(dao.list_members_by_tier(SubscriptionType::Premium, 0, 10), vec![first]);
+            assert_eq!(dao.list_members_by_role(Roles::Mentor, 0, 10), vec![first]);
+            assert_eq!(dao.member_count(), 2);
+
+            // indexing a member again only refreshes its groups
+            member.mentor = false;
+            dao.members.insert(first, &member);
+            dao.index_member(first);
+            assert_eq!(dao.member_count(), 2);
+            assert_eq!(dao.count_members_by_role(Roles::Mentor), 0);
+            assert_eq!(dao.count_members_by_tier(SubscriptionType::Premium), 1);
+        }
+
+        #[ink::test]
+        fn mentor_accepts_mentees_up_to_capacity() {
+            let accounts = ink::env::test::default_accounts();
+            let mut dao = dao();
+            add_member(&mut dao, accounts.bob, true);
+            add_member(&mut dao, accounts.charlie, false);
+            add_member(&mut dao, accounts.django, false);
+
+            ink::env::test::set_caller(accounts.bob);
+            assert_eq!(
+                dao.set_mentor_capacity(MAX_MENTEES + 1),
+                Err(Error::InvalidMentorCapacity)
+            );
+            assert_eq!(dao.set_mentor_capacity(1), Ok(()));
+
+            ink::env::test::set_caller(accounts.charlie);
+            assert_eq!(dao.request_mentor(accounts.django), Err(Error::NotAMentor));
+            assert_eq!(dao.request_mentor(accounts.bob), Ok(()));
+            ink::env::test::set_caller(accounts.django);
+            assert_eq!(dao.request_mentor(accounts.bob), Ok(()));
+
+            ink::env::test::set_caller(accounts.bob);
+            assert_eq!(dao.accept_mentee(accounts.eve), Err(Error::MentorshipRequestNotFound));
+            assert_eq!(dao.accept_mentee(accounts.charlie), Ok(()));
+            assert_eq!(dao.accept_mentee(accounts.django), Err(Error::MentorAtCapacity));
+            assert_eq!(dao.get_mentees(accounts.bob), vec![accounts.charlie]);
+            assert_eq!(dao.get_mentor_of(accounts.charlie), Some(accounts.bob));
+            assert_eq!(dao.get_mentor_of(accounts.django), None);
+        }
+
+        #[ink::test]
+        fn confirmed_sessions_accrue_within_the_epoch_budget() {
+            let accounts = ink::env::test::default_accounts();
+            let mut dao = dao();
+            dao.mentor_rewards_config = MentorRewardsConfig {
+                session_rate: U256::from(10),
+                epoch_length: 100,
+                epoch_budget: U256::from(15),
             };
-            dao.new_subscription(b"test_user".to_vec(), subscription, false, false);
-            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
-            assert_eq!(1, emitted_events.len());
+            dao.start_epoch();
+            add_member(&mut dao, accounts.bob, true);
+            add_member(&mut dao, accounts.charlie, false);
+            ink::env::test::set_caller(accounts.bob);
+            dao.set_mentor_capacity(1).unwrap();
+            ink::env::test::set_caller(accounts.charlie);
+            dao.request_mentor(accounts.bob).unwrap();
+            ink::env::test::set_caller(accounts.bob);
+            dao.accept_mentee(accounts.charlie).unwrap();
+
+            assert_eq!(
+                dao.log_session(accounts.django, 60, Vec::new()),
+                Err(Error::NotYourMentee)
+            );
+            let first = dao.log_session(accounts.charlie, 60, Vec::new()).unwrap();
+            let second = dao.log_session(accounts.charlie, 30, Vec::new()).unwrap();
+            let sessions = dao.get_mentor_sessions(accounts.bob, 0, 10);
+            assert_eq!(sessions.len(), 2);
+            assert_eq!(sessions[0].session_id, first);
+            assert_eq!(sessions[1].session_id, second);
+            assert_eq!(dao.get_mentor_sessions(accounts.bob, 1, 10).len(), 1);
+
+            assert_eq!(dao.confirm_session(first), Err(Error::NotAnAuthorisedUser));
+            ink::env::test::set_caller(accounts.charlie);
+            assert_eq!(dao.confirm_session(first), Ok(()));
+            assert_eq!(dao.confirm_session(first), Err(Error::SessionAlreadyConfirmed));
+            assert_eq!(dao.confirm_session(second), Ok(()));
+
+            // the second session only gets what is left of the epoch budget
+            assert_eq!(dao.get_session(first).unwrap().reward, U256::from(10));
+            assert_eq!(dao.get_session(second).unwrap().reward, U256::from(5));
+            assert_eq!(dao.get_mentor_rewards(accounts.bob), U256::from(15));
         }
 
         #[ink::test]
-        fn test_update_subscription() {
-            //fund caller wallet
-            let mut dao = Dao::new();
-            let caller = ink::env::test::default_accounts().alice;
+        fn epoch_budget_renews_after_the_epoch_length() {
+            let accounts = ink::env::test::default_accounts();
+            let mut dao = dao();
+            dao.mentor_rewards_config = MentorRewardsConfig {
+                session_rate: U256::from(10),
+                epoch_length: 100,
+                epoch_budget: U256::from(10),
+            };
+            dao.start_epoch();
+            dao.accrue_mentor_reward(accounts.bob);
+            assert_eq!(dao.get_epoch_budget_left(), U256::zero());
+
+            for _ in 0..99 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+            assert_eq!(dao.accrue_mentor_reward(accounts.bob), U256::zero());
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(dao.get_epoch_budget_left(), U256::from(10));
+            assert_eq!(dao.accrue_mentor_reward(accounts.bob), U256::from(10));
+
+            // a new configuration starts a new epoch from the current block
+            dao.mentor_rewards_config.epoch_length = 1000;
+            dao.mentor_rewards_config.epoch_budget = U256::from(30);
+            dao.start_epoch();
+            assert_eq!(dao.get_epoch_budget_left(), U256::from(30));
+            for _ in 0..100 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+            assert_eq!(dao.accrue_mentor_reward(accounts.bob), U256::from(10));
+            assert_eq!(dao.get_epoch_budget_left(), U256::from(20));
+        }
+    }
+
+    /// End-to-end tests, run against a node with
+    /// `cargo test --features e2e-tests`
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test]
+        async fn subscription_without_allowance_is_not_created<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            // given
+            let mut erc20_constructor = MyErc20Ref::new(U256::from(1_000_000_000));
+            let erc20 = client
+                .instantiate("my_erc20", &ink_e2e::alice(), &mut erc20_constructor)
+                .submit()
+                .await
+                .expect("my_erc20 instantiate failed");
+            let mut governance_constructor = GovernanceRef::new(50);
+            let governance = client
+                .instantiate("governance", &ink_e2e::alice(), &mut governance_constructor)
+                .submit()
+                .await
+                .expect("governance instantiate failed");
+            let mut constructor = DaoRef::from_addresses(
+                erc20.addr,
+                governance.addr,
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            );
+            let dao = client
+                .instantiate("my_edh", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("dao instantiate failed");
+            let mut call_builder = dao.call_builder::<Dao>();
+
+            // when bob subscribes without approving the DAO to spend his tokens
+            let new_subscription = call_builder.new_subscription(
+                b"bob".to_vec(),
+                SubscriptionType::Premium,
+                BillingPeriod::Monthly,
+                false,
+                false,
+            );
+            let result = client
+                .call(&ink_e2e::bob(), &new_subscription)
+                .submit()
+                .await;
+
+            // then the call is reverted and no member is created
+            assert!(
+                result.is_err(),
+                "subscription without allowance should fail"
+            );
+            let bob_account = ink_e2e::address::<ink::env::DefaultEnvironment>(
+                ink_e2e::Sr25519Keyring::Bob,
+            );
+            let get_user = call_builder.get_user(bob_account);
+            let user = client.call(&ink_e2e::bob(), &get_user).dry_run().await?;
+            assert!(user.return_value().is_none());
+            let member_count = call_builder.member_count();
+            let count = client.call(&ink_e2e::bob(), &member_count).dry_run().await?;
+            assert_eq!(count.return_value(), 0);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn cancelled_member_is_archived<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            // given a DAO registered as the governance's DAO
+            let mut erc20_constructor = MyErc20Ref::new(U256::from(1_000_000_000));
+            let erc20 = client
+                .instantiate("my_erc20", &ink_e2e::alice(), &mut erc20_constructor)
+                .submit()
+                .await
+                .expect("my_erc20 instantiate failed");
+            let mut governance_constructor = GovernanceRef::new(50);
+            let governance = client
+                .instantiate("governance", &ink_e2e::alice(), &mut governance_constructor)
+                .submit()
+                .await
+                .expect("governance instantiate failed");
+            let mut constructor = DaoRef::from_addresses(
+                erc20.addr,
+                governance.addr,
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            );
+            let dao = client
+                .instantiate("my_edh", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("dao instantiate failed");
+            let set_dao = governance
+                .call_builder::<governance::Governance>()
+                .set_dao(dao.addr);
+            client
+                .call(&ink_e2e::alice(), &set_dao)
+                .submit()
+                .await
+                .expect("set_dao failed");
+            let mut call_builder = dao.call_builder::<Dao>();
+
+            // and a member on the free tier, which needs no payment
+            let new_subscription = call_builder.new_subscription(
+                b"bob".to_vec(),
+                SubscriptionType::Free,
+                BillingPeriod::Monthly,
+                false,
+                false,
+            );
+            client
+                .call(&ink_e2e::bob(), &new_subscription)
+                .submit()
+                .await
+                .expect("new_subscription failed");
+
+            // when the member cancels
+            let cancel = call_builder.cancel_subscription();
+            client
+                .call(&ink_e2e::bob(), &cancel)
+                .submit()
+                .await
+                .expect("cancel_subscription failed");
+
+            // then the record is moved to the archive
+            let bob_account = ink_e2e::address::<ink::env::DefaultEnvironment>(
+                ink_e2e::Sr25519Keyring::Bob,
+            );
+            let get_user = call_builder.get_user(bob_account);
+            let user = client.call(&ink_e2e::bob(), &get_user).dry_run().await?;
+            assert!(user.return_value().is_none());
+            let get_archived_user = call_builder.get_archived_user(bob_account);
+            let archived = client
+                .call(&ink_e2e::bob(), &get_archived_user)
+                .dry_run()
+                .await?;
+            assert!(archived.return_value().is_some());
+            let member_count = call_builder.member_count();
+            let count = client.call(&ink_e2e::bob(), &member_count).dry_run().await?;
+            assert_eq!(count.return_value(), 0);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn cancelled_paid_subscription_is_refunded<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            // given a DAO registered as the governance's DAO
+            let mut erc20_constructor = MyErc20Ref::new(U256::from(1_000_000_000));
+            let erc20 = client
+                .instantiate("my_erc20", &ink_e2e::alice(), &mut erc20_constructor)
+                .submit()
+                .await
+                .expect("my_erc20 instantiate failed");
+            let mut governance_constructor = GovernanceRef::new(50);
+            let governance = client
+                .instantiate("governance", &ink_e2e::alice(), &mut governance_constructor)
+                .submit()
+                .await
+                .expect("governance instantiate failed");
+            let mut constructor = DaoRef::from_addresses(
+                erc20.addr,
+                governance.addr,
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            );
+            let dao = client
+                .instantiate("my_edh", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("dao instantiate failed");
+            let set_dao = governance
+                .call_builder::<governance::Governance>()
+                .set_dao(dao.addr);
+            client
+                .call(&ink_e2e::alice(), &set_dao)
+                .submit()
+                .await
+                .expect("set_dao failed");
+            let mut token = erc20.call_builder::<my_erc20::MyErc20>();
+            let mut call_builder = dao.call_builder::<Dao>();
+            let bob_account = ink_e2e::address::<ink::env::DefaultEnvironment>(
+                ink_e2e::Sr25519Keyring::Bob,
+            );
+
+            // and bob holding tokens the DAO may spend
+            let initial_balance = U256::from(10_000);
+            let transfer = token.transfer(bob_account, initial_balance);
+            client
+                .call(&ink_e2e::alice(), &transfer)
+                .submit()
+                .await
+                .expect("transfer failed");
+            let approve = token.approve(dao.addr, initial_balance);
+            client
+                .call(&ink_e2e::bob(), &approve)
+                .submit()
+                .await
+                .expect("approve failed");
+
+            // and a member on the basic tier
+            let new_subscription = call_builder.new_subscription(
+                b"bob".to_vec(),
+                SubscriptionType::Basic,
+                BillingPeriod::Monthly,
+                false,
+                false,
+            );
+            client
+                .call(&ink_e2e::bob(), &new_subscription)
+                .submit()
+                .await
+                .expect("new_subscription failed");
+            let balance_of = token.balance_of(bob_account);
+            let paid_balance = client
+                .call(&ink_e2e::bob(), &balance_of)
+                .dry_run()
+                .await?
+                .return_value();
+            assert!(paid_balance < initial_balance);
+
+            // when the member cancels
+            let cancel = call_builder.cancel_subscription();
+            client
+                .call(&ink_e2e::bob(), &cancel)
+                .submit()
+                .await
+                .expect("cancel_subscription failed");
+
+            // then the unused part of the price is refunded and the member archived
+            let refunded_balance = client
+                .call(&ink_e2e::bob(), &balance_of)
+                .dry_run()
+                .await?
+                .return_value();
+            assert!(refunded_balance > paid_balance);
+            assert!(refunded_balance <= initial_balance);
+            let get_archived_user = call_builder.get_archived_user(bob_account);
+            let archived = client
+                .call(&ink_e2e::bob(), &get_archived_user)
+                .dry_run()
+                .await?;
+            assert!(archived.return_value().is_some());
+
+            Ok(())
         }
     }
 }
@@ -1,22 +1,79 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 pub use self::governance::{
+	ApprovalThreshold,
+	Election,
+	Error,
 	Governance,
 	GovernanceRef,
+	ProposalStatus,
 	ProposalType,
+	UpgradeTarget,
+	VotingMode,
+	VotingRules,
 };
 
 #[ink::contract]
 mod governance {
-use ink::{ prelude::vec::Vec, storage::Mapping, U256};
+use ink::{ prelude::vec::Vec, storage::Mapping, H256, U256};
+use ink::storage::{traits::ManualKey, Lazy};
+use ink::codegen::TraitCallBuilder;
+use ink::env::call::FromAddr;
+use my_erc20::MyErc20Ref;
 
+	/// Version of the storage layout written by this code, see `migrate`
+	pub const STORAGE_VERSION: u32 = 1;
+	/// Storage key of the version marker, kept out of the root struct so that it
+	/// can be read whatever the layout of the root
+	pub const STORAGE_VERSION_KEY: u32 = 0x7665_7273;
+
+	/// Number of blocks after the end of the vote during which a succeeded
+	/// proposal can be executed, 14 days of 3 second blocks
+	pub const EXECUTION_PERIOD: BlockNumber = 403_200;
+
+	/// Lifecycle of a proposal:
+	/// `Pending -> Active -> Succeeded | Defeated`, then `Succeeded -> [Queued ->] Executed`.
+	/// Unfinished proposals can be `Cancelled`, succeeded ones not executed in time
+	/// are `Expired`.
     #[derive(Debug, Clone, PartialEq)]
 	#[ink::scale_derive(Encode, Decode, TypeInfo)]
 	#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
 	pub enum ProposalStatus {
-		Submitted,
-		Approved,
-		Rejected,
+		/// Created, the vote opens at `VoteInfos::start`
+		Pending,
+		/// The vote is running
+		Active,
+		/// The vote is over and the proposal passed
+		Succeeded,
+		/// The vote is over and the proposal did not pass
+		Defeated,
+		/// Succeeded and waiting for its execution
+		Queued,
 		Executed,
+		Cancelled,
+		/// Succeeded but not executed within `EXECUTION_PERIOD`
+		Expired,
+	}
+
+	impl ProposalStatus {
+		/// Whether a proposal can move from this status to `next`
+		pub fn can_transition_to(&self, next: &ProposalStatus) -> bool {
+			use ProposalStatus::*;
+			matches!(
+				(self, next),
+				(Pending, Active)
+					| (Pending, Cancelled)
+					| (Active, Succeeded)
+					| (Active, Defeated)
+					| (Active, Cancelled)
+					| (Succeeded, Queued)
+					| (Succeeded, Executed)
+					| (Succeeded, Cancelled)
+					| (Succeeded, Expired)
+					| (Queued, Executed)
+					| (Queued, Cancelled)
+					| (Queued, Expired)
+			)
+		}
 	}
 
     #[derive(Debug, Clone, PartialEq)]
@@ -26,6 +83,120 @@ use ink::{ prelude::vec::Vec, storage::Mapping, U256};
 		Spending,
 		NewCouncilvoter,
 		NewMentor,
+		TierChange,
+		PriceChange,
+		RemoveMentor,
+		RemoveCouncil,
+		MentorRewards,
+		CancelStream,
+		VotingWeights,
+		VotingMode,
+		VotingRules,
+		Upgrade { target: UpgradeTarget, code_hash: H256 },
+		SubscriptionRules,
+	}
+
+	impl ProposalType {
+		/// Index of the proposal type, whatever its fields, keying its voting rules
+		pub fn index(&self) -> u8 {
+			match self {
+				ProposalType::Spending => 0,
+				ProposalType::NewCouncilvoter => 1,
+				ProposalType::NewMentor => 2,
+				ProposalType::TierChange => 3,
+				ProposalType::PriceChange => 4,
+				ProposalType::RemoveMentor => 5,
+				ProposalType::RemoveCouncil => 6,
+				ProposalType::MentorRewards => 7,
+				ProposalType::CancelStream => 8,
+				ProposalType::VotingWeights => 9,
+				ProposalType::VotingMode => 10,
+				ProposalType::VotingRules => 11,
+				ProposalType::Upgrade { .. } => 12,
+				ProposalType::SubscriptionRules => 13,
+			}
+		}
+	}
+
+	/// Share of the votes cast that must approve a proposal
+	#[derive(Debug, Clone, PartialEq)]
+	#[ink::scale_derive(Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+	pub enum ApprovalThreshold {
+		/// More yes than no votes
+		SimpleMajority,
+		/// At least 60% of the votes cast
+		SixtyPercent,
+		/// At least two thirds of the votes cast
+		TwoThirds,
+	}
+
+	impl ApprovalThreshold {
+		/// Whether `yes_votes` out of `turnout` votes cast reach the threshold
+		pub fn is_met(&self, yes_votes: U256, turnout: U256) -> bool {
+			if yes_votes == U256::zero() {
+				return false;
+			}
+			let no_votes = turnout.saturating_sub(yes_votes);
+			match self {
+				ApprovalThreshold::SimpleMajority => yes_votes > no_votes,
+				ApprovalThreshold::SixtyPercent => {
+					yes_votes.saturating_mul(U256::from(100))
+						>= turnout.saturating_mul(U256::from(60))
+				},
+				ApprovalThreshold::TwoThirds => {
+					yes_votes.saturating_mul(U256::from(3)) >= turnout.saturating_mul(U256::from(2))
+				},
+			}
+		}
+	}
+
+	/// Quorum and approval threshold a proposal type must reach to pass
+	#[derive(Debug, Clone, PartialEq)]
+	#[ink::scale_derive(Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+	pub struct VotingRules {
+		/// Minimum turnout, in percent of the total voting power
+		pub quorum_percent: u8,
+		pub approval: ApprovalThreshold,
+	}
+
+	impl VotingRules {
+		/// Rules applied to a proposal type until governance changes them.
+		/// Treasury and upgrade proposals need a larger turnout and a qualified majority.
+		pub fn default_for(proposal_type: &ProposalType) -> Self {
+			match proposal_type {
+				ProposalType::Spending | ProposalType::MentorRewards | ProposalType::CancelStream => {
+					VotingRules { quorum_percent: 20, approval: ApprovalThreshold::SixtyPercent }
+				},
+				ProposalType::Upgrade { .. } | ProposalType::VotingRules | ProposalType::VotingMode => {
+					VotingRules { quorum_percent: 33, approval: ApprovalThreshold::TwoThirds }
+				},
+				_ => VotingRules { quorum_percent: 10, approval: ApprovalThreshold::SimpleMajority },
+			}
+		}
+	}
+
+	/// Source of the voting power of registered voters
+	#[derive(Debug, Clone, Default, PartialEq)]
+	#[ink::scale_derive(Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+	pub enum VotingMode {
+		/// `Voter::voting_power`, set by the DAO from the membership
+		#[default]
+		Membership,
+		/// `MyErc20` balance of the voter at the block the proposal was created
+		TokenBalance { token: Address },
+	}
+
+	/// Contract whose code an `Upgrade` proposal replaces
+	#[derive(Debug, Clone, PartialEq)]
+	#[ink::scale_derive(Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+	pub enum UpgradeTarget {
+		Dao,
+		Governance,
+		Token,
 	}
 
     #[derive(Debug, Clone)]
@@ -73,6 +244,13 @@ use ink::{ prelude::vec::Vec, storage::Mapping, U256};
 
 		// U256 representing the total votes against this proposal
 		pub no_votes: U256,
+
+		// Voting power of all the voters when the proposal was created, the quorum
+		// is measured against it
+		pub total_power: U256,
+
+		// Rules of the proposal type when the proposal was created
+		pub rules: VotingRules,
 	}
 
     /// Defines an event that is emitted every time a voter voted.  
@@ -95,37 +273,90 @@ use ink::{ prelude::vec::Vec, storage::Mapping, U256};
 		pub status: ProposalStatus,
 		/// Information related to voting
 		pub votes: Option<VoteInfos>,
+		/// Block at which the proposal was created, token balances are read at it
+		pub created_at: BlockNumber,
 		// Information relative to proposal execution if approved
 		pub transaction: Option<Transaction>,
         // owner of the proposal
         pub owner: Address,
 	}
 
+    impl Proposal {
+		/// Moves the proposal to `next`, if the lifecycle allows it
+		fn transition(&mut self, next: ProposalStatus) -> Result<(), Error> {
+			if !self.status.can_transition_to(&next) {
+				return Err(Error::InvalidTransition);
+			}
+			self.status = next;
+			Ok(())
+		}
+
+		/// Applies the transitions driven by the passing of time at block `now`:
+		/// opening and closing the vote, and expiring unexecuted proposals
+		fn refresh_status(&mut self, now: BlockNumber) -> Result<(), Error> {
+			let vote_infos = self.votes.clone().ok_or(Error::ProblemWithTheContract)?;
+			if self.status == ProposalStatus::Pending && now >= vote_infos.start {
+				self.transition(ProposalStatus::Active)?;
+			}
+			if self.status == ProposalStatus::Active && now > vote_infos.end {
+				self.transition(vote_infos.outcome())?;
+			}
+			if matches!(self.status, ProposalStatus::Succeeded | ProposalStatus::Queued)
+				&& now > vote_infos.end.saturating_add(EXECUTION_PERIOD)
+			{
+				self.transition(ProposalStatus::Expired)?;
+			}
+			Ok(())
+		}
+	}
+
     impl VoteInfos {
-		fn get_status(&self, mut proposal: Proposal) -> Proposal {
-			if proposal.status == ProposalStatus::Submitted {
-				if self.yes_votes > self.no_votes {
-					proposal.status = ProposalStatus::Approved;
-				} else {
-					proposal.status = ProposalStatus::Rejected;
-				}
-			};
-			proposal
+		/// Whether enough voting power took part in the vote
+		fn quorum_reached(&self) -> bool {
+			let turnout = self.yes_votes.saturating_add(self.no_votes);
+			turnout.saturating_mul(U256::from(100))
+				>= self.total_power.saturating_mul(U256::from(self.rules.quorum_percent))
 		}
 
-		fn update_votes(&mut self, approved: bool, voter: Voter) {
+		/// Result of a finished vote
+		fn outcome(&self) -> ProposalStatus {
+			let turnout = self.yes_votes.saturating_add(self.no_votes);
+			if self.quorum_reached() && self.rules.approval.is_met(self.yes_votes, turnout) {
+				ProposalStatus::Succeeded
+			} else {
+				ProposalStatus::Defeated
+			}
+		}
+
+		fn update_votes(&mut self, approved: bool, voting_power: U256) {
 			match approved {
 				true => {
 					self.yes_votes =
-						self.yes_votes.saturating_add(voter.voting_power);
+						self.yes_votes.saturating_add(voting_power);
 				},
 				false => {
-					self.no_votes = self.no_votes.saturating_add(voter.voting_power);
+					self.no_votes = self.no_votes.saturating_add(voting_power);
 				},
 			};
 		}
 	}
 
+    /// Election round filling the council seats for the next term
+	#[derive(Debug, Clone)]
+	#[ink::scale_derive(Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+	pub struct Election {
+		pub election_id: u32,
+		// Number of council seats to fill
+		pub seats: u32,
+		// Nominations and votes are accepted until this block
+		pub end: BlockNumber,
+		// Members nominated for a seat
+		pub candidates: Vec<Address>,
+		// Set once the results have been collected
+		pub closed: bool,
+	}
+
     #[derive(Debug, Clone, Default)]
 	#[ink::scale_derive(Encode, Decode, TypeInfo)]
 	#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
@@ -154,6 +385,38 @@ use ink::{ prelude::vec::Vec, storage::Mapping, U256};
 
 		// Number ofProposals created so far
 		proposal_count: u32,
+
+		// Council elections
+		elections: Mapping<u32, Election>,
+
+		// Votes received by each candidate of an election
+		election_votes: Mapping<(u32, Address), U256>,
+
+		// Members who already voted in an election
+		election_voters: Mapping<(u32, Address), ()>,
+
+		// Number of elections created so far
+		election_count: u32,
+
+		// Voters who already voted on a proposal
+		proposal_voters: Mapping<(u32, Address), ()>,
+
+		// DAO contract that instantiated the governance, the only caller allowed
+		// to run elections
+		dao: Address,
+
+		// Version of the storage layout, bumped by `migrate` after an upgrade
+		storage_version: Lazy<u32, ManualKey<STORAGE_VERSION_KEY>>,
+
+		// Source of the voting power counted by `vote`
+		voting_mode: VotingMode,
+
+		// Sum of the voting power of the registered voters
+		total_voting_power: U256,
+
+		// Voting rules set by governance, keyed by `ProposalType::index`.
+		// Types without an entry use `VotingRules::default_for`.
+		voting_rules: Mapping<u8, VotingRules>,
 	}
 
 
@@ -161,17 +424,29 @@ use ink::{ prelude::vec::Vec, storage::Mapping, U256};
         /// Constructor that initializes the `bool` value to the given `init_value`.
         #[ink(constructor)]
         pub fn new(voting_period: BlockNumber) -> Self {
-            Self {
+            let mut governance = Self {
 				proposals: Mapping::default(),
                 voters: Mapping::default(),
 				voting_period,
 				proposal_count: 0,
-			}
+				elections: Mapping::default(),
+				election_votes: Mapping::default(),
+				election_voters: Mapping::default(),
+				election_count: 0,
+				proposal_voters: Mapping::default(),
+				dao: Self::env().caller(),
+				storage_version: Lazy::new(),
+				voting_mode: VotingMode::Membership,
+				total_voting_power: U256::zero(),
+				voting_rules: Mapping::default(),
+			};
+			governance.storage_version.set(&STORAGE_VERSION);
+			governance
         }
 
-            /// A message that can be called on instantiated contracts.
-        /// This one flips the value of the stored `bool` from `true`
-        /// to `false` and vice versa.
+        /// Submits a proposal to the vote. Only the DAO can create proposals: it
+        /// checks who may request them and executes them once they succeed.
+        /// `proposer` is the member who requested it, who can cancel it.
         #[ink(message)]
         pub fn create_proposal(
             &mut self,
@@ -179,7 +454,9 @@ use ink::{ prelude::vec::Vec, storage::Mapping, U256};
             proposal_type: ProposalType,
             beneficiary: Option<Address>,
             amount: U256,
-        ) -> u32 {
+            proposer: Address,
+        ) -> Result<u32, Error> {
+            self.ensure_dao()?;
             let proposal_id = self.proposal_count;
             let transaction = match proposal_type {
                 ProposalType::Spending => {
@@ -188,49 +465,198 @@ use ink::{ prelude::vec::Vec, storage::Mapping, U256};
                         amount,
                     })
                 }
-                _ => None,
+                // Other proposals keep the member they target, if any
+                _ => beneficiary.map(|beneficiary| Transaction { beneficiary, amount }),
             };
 
+            // The vote opens on the next block, once the balances the voting power
+            // is read from are settled
+            let created_at = self.env().block_number();
+            let start = created_at.saturating_add(1);
+            let rules = self.get_voting_rules(proposal_type.clone());
+            let total_power = self.total_power();
             let proposal = Proposal {
                 basic_infos: BasicInfo {
                     description,
                     proposal_type,
                     proposal_id,
                 },
-                status: ProposalStatus::Submitted,
-                votes: None,
+                status: ProposalStatus::Pending,
+                votes: Some(VoteInfos {
+                    start,
+                    end: start.saturating_add(self.voting_period),
+                    yes_votes: U256::zero(),
+                    no_votes: U256::zero(),
+                    total_power,
+                    rules,
+                }),
+                created_at,
                 transaction,
-                owner: ink::env::caller(),
+                owner: proposer,
             };
             self.proposals.insert(proposal_id, &proposal);
             self.proposal_count += 1;
-            proposal_id
+            Ok(proposal_id)
         }
 
-        #[ink(message)]
-		pub fn vote(&mut self, proposal_id: u32, approve: bool) -> Result<(), Error> {
-			let caller = self.env().caller();
+		/// Records the vote of `voter`, forwarded by the DAO on behalf of its member.
+		/// Votes only go through the DAO, which checks the member is in good standing.
+		#[ink(message)]
+		pub fn vote_for(&mut self, voter: Address, proposal_id: u32, approve: bool) -> Result<(), Error> {
+			self.ensure_dao()?;
+			self.cast_vote(voter, proposal_id, approve)
+		}
+
+		/// Registers a DAO member as a voter. Only the DAO can register voters.
+		#[ink(message)]
+		pub fn register_voter(&mut self, voter: Address, voting_power: U256) -> Result<(), Error> {
+			self.ensure_dao()?;
+			if self.voters.contains(voter) {
+				return Err(Error::VoterAlreadyRegistered);
+			}
+			self.voters.insert(voter, &Voter { voting_power, last_vote: 0 });
+			self.total_voting_power = self.total_voting_power.saturating_add(voting_power);
+			Ok(())
+		}
+
+		/// Updates the voting power of a voter, e.g. after a membership change
+		#[ink(message)]
+		pub fn update_voter(&mut self, voter: Address, voting_power: U256) -> Result<(), Error> {
+			self.ensure_dao()?;
+			let mut registered = self.voters.get(voter).ok_or(Error::VoterNotFound)?;
+			self.total_voting_power = self
+				.total_voting_power
+				.saturating_sub(registered.voting_power)
+				.saturating_add(voting_power);
+			registered.voting_power = voting_power;
+			self.voters.insert(voter, &registered);
+			Ok(())
+		}
+
+		/// Removes a voter who left the DAO
+		#[ink(message)]
+		pub fn remove_voter(&mut self, voter: Address) -> Result<(), Error> {
+			self.ensure_dao()?;
+			let registered = self.voters.get(voter).ok_or(Error::VoterNotFound)?;
+			self.total_voting_power = self.total_voting_power.saturating_sub(registered.voting_power);
+			self.voters.remove(voter);
+			Ok(())
+		}
+
+		#[ink(message)]
+		pub fn get_voter(&self, voter: Address) -> Option<Voter> {
+			self.voters.get(voter)
+		}
+
+		#[ink(message)]
+		pub fn get_total_voting_power(&self) -> U256 {
+			self.total_voting_power
+		}
+
+		/// Sets the quorum and approval threshold of a proposal type, once approved
+		/// by a proposal. Proposals already created keep their rules.
+		#[ink(message)]
+		pub fn set_voting_rules(
+			&mut self,
+			proposal_type: ProposalType,
+			rules: VotingRules,
+		) -> Result<(), Error> {
+			self.ensure_dao()?;
+			if rules.quorum_percent > 100 {
+				return Err(Error::InvalidVotingRules);
+			}
+			self.voting_rules.insert(proposal_type.index(), &rules);
+			Ok(())
+		}
+
+		#[ink(message)]
+		pub fn get_voting_rules(&self, proposal_type: ProposalType) -> VotingRules {
+			self.voting_rules
+				.get(proposal_type.index())
+				.unwrap_or_else(|| VotingRules::default_for(&proposal_type))
+		}
+
+		/// Switches the source of the voting power, once approved by a proposal
+		#[ink(message)]
+		pub fn set_voting_mode(&mut self, voting_mode: VotingMode) -> Result<(), Error> {
+			self.ensure_dao()?;
+			self.voting_mode = voting_mode;
+			Ok(())
+		}
+
+		#[ink(message)]
+		pub fn get_voting_mode(&self) -> VotingMode {
+			self.voting_mode.clone()
+		}
+
+		/// Voting power all the voters hold now: the registered voting power, or the
+		/// token supply when votes are weighted by token balance. The DAO treasury
+		/// cannot vote, so its balance is left out of the quorum base.
+		fn total_power(&self) -> U256 {
+			match &self.voting_mode {
+				VotingMode::Membership => self.total_voting_power,
+				VotingMode::TokenBalance { token } => {
+					let token: MyErc20Ref = FromAddr::from_addr(*token);
+					let supply = token
+						.call()
+						.total_supply()
+						.ref_time_limit(100000000000)
+						.proof_size_limit(10000000)
+						.storage_deposit_limit(500000000000u128.into())
+						.invoke();
+					let treasury = token
+						.call()
+						.balance_of_at(self.dao, self.env().block_number())
+						.ref_time_limit(100000000000)
+						.proof_size_limit(10000000)
+						.storage_deposit_limit(500000000000u128.into())
+						.invoke();
+					supply.saturating_sub(treasury)
+				}
+			}
+		}
+
+		/// Voting power of a registered voter on a proposal. In token mode, tokens
+		/// acquired after the proposal was created do not count.
+		fn voting_power(&self, account: Address, voter: &Voter, proposal: &Proposal) -> U256 {
+			match &self.voting_mode {
+				VotingMode::Membership => voter.voting_power,
+				VotingMode::TokenBalance { token } => {
+					let token: MyErc20Ref = FromAddr::from_addr(*token);
+					token
+						.call()
+						.balance_of_at(account, proposal.created_at)
+						.ref_time_limit(100000000000)
+						.proof_size_limit(10000000)
+						.storage_deposit_limit(500000000000u128.into())
+						.invoke()
+				}
+			}
+		}
+
+		fn cast_vote(&mut self, caller: Address, proposal_id: u32, approve: bool) -> Result<(), Error> {
 			let current_block = self.env().block_number();
 			let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
-			let mut vote_infos = proposal.votes.clone().ok_or(Error::ProblemWithTheContract)?;
-
-			if current_block > vote_infos.end {
-				// Update the Proposal status if needed
-				proposal = vote_infos.get_status(proposal);
-				self.proposals.insert(proposal.basic_infos.proposal_id, &proposal);
-				return Err(Error::VotingPeriodEnded);
+			proposal.refresh_status(current_block)?;
+			match proposal.status {
+				ProposalStatus::Active => {}
+				ProposalStatus::Pending => return Err(Error::VotingPeriodNotStarted),
+				_ => return Err(Error::VotingPeriodEnded),
 			}
+			let mut vote_infos = proposal.votes.clone().ok_or(Error::ProblemWithTheContract)?;
 
 			let voter = self.voters.get(caller).ok_or(Error::VoterNotFound)?;
 
-			if voter.last_vote >= vote_infos.start {
+			if self.proposal_voters.contains((proposal_id, caller)) {
 				return Err(Error::AlreadyVoted);
 			}
 
-			vote_infos.update_votes(approve, voter.clone());
+			let voting_power = self.voting_power(caller, &voter, &proposal);
+			vote_infos.update_votes(approve, voting_power);
 			proposal.votes = Some(vote_infos);
 
 			self.proposals.insert(proposal_id, &proposal);
+			self.proposal_voters.insert((proposal_id, caller), &());
 
 			self.voters.insert(
 				caller,
@@ -242,43 +668,228 @@ use ink::{ prelude::vec::Vec, storage::Mapping, U256};
 			Ok(())
 		}
 
+		/// Moves a finished proposal forward: `executed == false` queues a succeeded
+		/// proposal, `executed == true` marks it executed, which only the DAO can do
+		/// as it runs the proposal. Fails unless the vote succeeded.
         #[ink(message)]
 		pub fn update_proposal_status(&mut self, proposal_id: u32, executed:bool) -> Result<(), Error> {
             let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
-            let vote_infos = proposal.votes.clone().ok_or(Error::ProblemWithTheContract)?;
-            
-			// Check the voting period
-			if self.env().block_number() <= vote_infos.end {
-				return Err(Error::VotingPeriodNotEnded);
-			}
+			proposal.refresh_status(self.env().block_number())?;
 
-			
-			if proposal.status == ProposalStatus::Executed {
-				return Err(Error::ProposalExecuted);
-			}			
+			match proposal.status {
+				ProposalStatus::Pending | ProposalStatus::Active => {
+					return Err(Error::VotingPeriodNotEnded)
+				}
+				ProposalStatus::Defeated => {
+					let vote_infos = proposal.votes.clone().ok_or(Error::ProblemWithTheContract)?;
+					if !vote_infos.quorum_reached() {
+						return Err(Error::QuorumNotReached);
+					}
+					return Err(Error::ProposalRejected)
+				}
+				ProposalStatus::Executed => return Err(Error::ProposalExecuted),
+				ProposalStatus::Cancelled => return Err(Error::ProposalCancelled),
+				ProposalStatus::Expired => return Err(Error::ProposalExpired),
+				ProposalStatus::Succeeded | ProposalStatus::Queued => {}
+			}
 
-			if vote_infos.yes_votes <= vote_infos.no_votes {
-				proposal.status = ProposalStatus::Rejected;
-				self.proposals.insert(proposal_id, &proposal);
-				return Err(Error::ProposalRejected);
+			if executed {
+				self.ensure_dao()?;
+				proposal.transition(ProposalStatus::Executed)?;
 			} else {
-				proposal.status = ProposalStatus::Approved;
-				self.proposals.insert(proposal_id, &proposal);				
+				proposal.transition(ProposalStatus::Queued)?;
 			}
-
-			if executed==true {
-				proposal.status = ProposalStatus::Executed;
-				self.proposals.insert(proposal_id, &proposal);
-			}           
-				
+			self.proposals.insert(proposal_id, &proposal);
             Ok(())
         }
 
+		/// Cancels a proposal that is not finished yet. Only the member who
+		/// proposed it or the DAO can cancel it.
+		#[ink(message)]
+		pub fn cancel_proposal(&mut self, proposal_id: u32) -> Result<(), Error> {
+			let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+			let caller = self.env().caller();
+			if caller != proposal.owner && caller != self.dao {
+				return Err(Error::NotProposalOwner);
+			}
+			proposal.refresh_status(self.env().block_number())?;
+			proposal.transition(ProposalStatus::Cancelled)?;
+			self.proposals.insert(proposal_id, &proposal);
+			Ok(())
+		}
+
+		/// Current status of a proposal, time-driven transitions included
+		#[ink(message)]
+		pub fn get_proposal_status(&self, proposal_id: u32) -> Result<ProposalStatus, Error> {
+			let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+			proposal.refresh_status(self.env().block_number())?;
+			Ok(proposal.status)
+		}
+
 		#[ink(message)]
 		pub fn get_proposal(&self, proposal_id: u32) -> Result<Proposal, Error> {
 			self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)
 		}
 
+		/// Opens an election for `seats` council seats, running until block `end`.
+		/// Only the DAO can open an election.
+		#[ink(message)]
+		pub fn create_election(&mut self, seats: u32, end: BlockNumber) -> Result<u32, Error> {
+			self.ensure_dao()?;
+			let election_id = self.election_count;
+			let election = Election {
+				election_id,
+				seats,
+				end,
+				candidates: Vec::new(),
+				closed: false,
+			};
+			self.elections.insert(election_id, &election);
+			self.election_count += 1;
+			Ok(election_id)
+		}
+
+		/// Registers `candidate` in an open election.
+		/// The DAO checks the candidate's eligibility before nominating them.
+		#[ink(message)]
+		pub fn nominate(&mut self, election_id: u32, candidate: Address) -> Result<(), Error> {
+			self.ensure_dao()?;
+			let mut election = self.open_election(election_id)?;
+			if election.candidates.contains(&candidate) {
+				return Err(Error::AlreadyNominated);
+			}
+			election.candidates.push(candidate);
+			self.elections.insert(election_id, &election);
+			Ok(())
+		}
+
+		/// Records the vote of `voter` for `candidate`, one vote per voter and election.
+		/// The DAO checks the voter's eligibility before forwarding the vote.
+		#[ink(message)]
+		pub fn vote_election(
+			&mut self,
+			election_id: u32,
+			voter: Address,
+			candidate: Address,
+		) -> Result<(), Error> {
+			self.ensure_dao()?;
+			let election = self.open_election(election_id)?;
+			if !election.candidates.contains(&candidate) {
+				return Err(Error::CandidateNotFound);
+			}
+			if self.election_voters.contains((election_id, voter)) {
+				return Err(Error::AlreadyVoted);
+			}
+			let votes = self.election_votes.get((election_id, candidate)).unwrap_or_default();
+			self.election_votes
+				.insert((election_id, candidate), &votes.saturating_add(U256::from(1)));
+			self.election_voters.insert((election_id, voter), &());
+
+			let current_block = self.env().block_number();
+			self.env().emit_event(Vote { who: Some(voter), when: Some(current_block) });
+			Ok(())
+		}
+
+		/// Closes an election once its voting period is over and returns the elected
+		/// candidates, the most voted first, at most `seats` of them.
+		#[ink(message)]
+		pub fn close_election(&mut self, election_id: u32) -> Result<Vec<Address>, Error> {
+			self.ensure_dao()?;
+			let mut election = self.elections.get(election_id).ok_or(Error::ElectionNotFound)?;
+			if election.closed {
+				return Err(Error::ElectionClosed);
+			}
+			if self.env().block_number() <= election.end {
+				return Err(Error::VotingPeriodNotEnded);
+			}
+			let mut results: Vec<(Address, U256)> = election
+				.candidates
+				.iter()
+				.map(|candidate| {
+					let votes = self.election_votes.get((election_id, *candidate)).unwrap_or_default();
+					(*candidate, votes)
+				})
+				.collect();
+			results.sort_by(|a, b| b.1.cmp(&a.1));
+			let winners = results
+				.into_iter()
+				.take(election.seats as usize)
+				.map(|(candidate, _)| candidate)
+				.collect();
+
+			election.closed = true;
+			self.elections.insert(election_id, &election);
+			Ok(winners)
+		}
+
+		#[ink(message)]
+		pub fn get_election(&self, election_id: u32) -> Result<Election, Error> {
+			self.elections.get(election_id).ok_or(Error::ElectionNotFound)
+		}
+
+		#[ink(message)]
+		pub fn get_election_votes(&self, election_id: u32, candidate: Address) -> U256 {
+			self.election_votes.get((election_id, candidate)).unwrap_or_default()
+		}
+
+		/// Hands the DAO role over to another account, e.g. to a DAO contract
+		/// connected to this governance after it was deployed
+		#[ink(message)]
+		pub fn set_dao(&mut self, dao: Address) -> Result<(), Error> {
+			self.ensure_dao()?;
+			self.dao = dao;
+			Ok(())
+		}
+
+		#[ink(message)]
+		pub fn get_dao(&self) -> Address {
+			self.dao
+		}
+
+		/// Replaces the code of the governance with the uploaded code `code_hash`,
+		/// once an `Upgrade` proposal is approved. The new code is used from the next
+		/// call on, which should be `migrate`.
+		#[ink(message)]
+		pub fn set_code(&mut self, code_hash: H256) -> Result<(), Error> {
+			self.ensure_dao()?;
+			self.env().set_code_hash(&code_hash).map_err(|_| Error::UpgradeFailed)
+		}
+
+		#[ink(message)]
+		pub fn get_storage_version(&self) -> u32 {
+			self.storage_version.get().unwrap_or_default()
+		}
+
+		/// Migrates the storage written by an older code to `STORAGE_VERSION`.
+		/// Does nothing if the storage is already up to date.
+		///
+		/// The root struct is decoded before any message runs, so an upgrade must
+		/// keep its layout: new state goes under its own key, in a `Mapping` or a
+		/// `Lazy` with a `ManualKey`, filled here from the old data.
+		#[ink(message)]
+		pub fn migrate(&mut self) -> Result<(), Error> {
+			self.ensure_dao()?;
+			if self.get_storage_version() < STORAGE_VERSION {
+				self.storage_version.set(&STORAGE_VERSION);
+			}
+			Ok(())
+		}
+
+		fn ensure_dao(&self) -> Result<(), Error> {
+			if self.env().caller() != self.dao {
+				return Err(Error::NotDao);
+			}
+			Ok(())
+		}
+
+		fn open_election(&self, election_id: u32) -> Result<Election, Error> {
+			let election = self.elections.get(election_id).ok_or(Error::ElectionNotFound)?;
+			if election.closed || self.env().block_number() > election.end {
+				return Err(Error::ElectionClosed);
+			}
+			Ok(election)
+		}
+
         
     }
 
@@ -293,6 +904,348 @@ use ink::{ prelude::vec::Vec, storage::Mapping, U256};
 		VotingPeriodNotEnded,
 		ProposalExecuted,
 		ProposalRejected,
+		NotDao,
+		ElectionNotFound,
+		ElectionClosed,
+		AlreadyNominated,
+		CandidateNotFound,
+		UpgradeFailed,
+		VoterAlreadyRegistered,
+		VotingPeriodNotStarted,
+		InvalidTransition,
+		ProposalCancelled,
+		ProposalExpired,
+		NotProposalOwner,
+		QuorumNotReached,
+		InvalidVotingRules,
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        type Environment = ink::env::DefaultEnvironment;
+
+        const VOTING_PERIOD: BlockNumber = 10;
+
+        fn set_caller(sender: Address) {
+            ink::env::test::set_caller(sender);
+        }
+
+        fn set_block(block: BlockNumber) {
+            ink::env::test::set_block_number::<Environment>(block);
+        }
+
+        /// Governance deployed by Alice acting as the DAO, with Bob and Charlie
+        /// registered as voters and one `Spending` proposal created at block 0
+        fn setup() -> (Governance, u32) {
+            let accounts = ink::env::test::default_accounts();
+            set_caller(accounts.alice);
+            set_block(0);
+            let mut governance = Governance::new(VOTING_PERIOD);
+            assert_eq!(governance.register_voter(accounts.bob, U256::from(2)), Ok(()));
+            assert_eq!(governance.register_voter(accounts.charlie, U256::from(1)), Ok(()));
+            let proposal_id = governance.create_proposal(
+                b"Fund a workshop".to_vec(),
+                ProposalType::Spending,
+                Some(accounts.eve),
+                U256::from(100),
+                accounts.charlie,
+            ).unwrap();
+            (governance, proposal_id)
+        }
+
+        #[test]
+        fn lifecycle_transitions_are_validated() {
+            use ProposalStatus::*;
+            assert!(Pending.can_transition_to(&Active));
+            assert!(Active.can_transition_to(&Succeeded));
+            assert!(Succeeded.can_transition_to(&Queued));
+            assert!(Queued.can_transition_to(&Executed));
+            assert!(Succeeded.can_transition_to(&Expired));
+            assert!(!Pending.can_transition_to(&Succeeded));
+            assert!(!Defeated.can_transition_to(&Executed));
+            assert!(!Executed.can_transition_to(&Cancelled));
+            assert!(!Cancelled.can_transition_to(&Active));
+            assert!(!Expired.can_transition_to(&Executed));
+        }
+
+        #[ink::test]
+        fn only_the_dao_creates_proposals() {
+            let accounts = ink::env::test::default_accounts();
+            let (mut governance, _) = setup();
+            set_caller(accounts.bob);
+            assert_eq!(
+                governance.create_proposal(
+                    b"Upgrade the DAO".to_vec(),
+                    ProposalType::Upgrade {
+                        target: UpgradeTarget::Dao,
+                        code_hash: H256::zero(),
+                    },
+                    None,
+                    U256::zero(),
+                    accounts.bob,
+                ),
+                Err(Error::NotDao)
+            );
+        }
+
+        #[ink::test]
+        fn create_proposal_opens_the_voting_window() {
+            let (governance, proposal_id) = setup();
+            let proposal = governance.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Pending);
+            assert_eq!(proposal.created_at, 0);
+            let votes = proposal.votes.unwrap();
+            assert_eq!(votes.start, 1);
+            assert_eq!(votes.end, 1 + VOTING_PERIOD);
+            assert_eq!(votes.yes_votes, U256::zero());
+            assert_eq!(votes.no_votes, U256::zero());
+
+            set_block(1);
+            assert_eq!(
+                governance.get_proposal_status(proposal_id),
+                Ok(ProposalStatus::Active)
+            );
+        }
+
+        #[ink::test]
+        fn succeeded_proposal_is_executed_once() {
+            let accounts = ink::env::test::default_accounts();
+            let (mut governance, proposal_id) = setup();
+
+            assert_eq!(
+                governance.vote_for(accounts.bob, proposal_id, true),
+                Err(Error::VotingPeriodNotStarted)
+            );
+            set_block(1);
+            assert_eq!(governance.vote_for(accounts.bob, proposal_id, true), Ok(()));
+            assert_eq!(
+                governance.vote_for(accounts.bob, proposal_id, true),
+                Err(Error::AlreadyVoted)
+            );
+            assert_eq!(governance.vote_for(accounts.charlie, proposal_id, false), Ok(()));
+            assert_eq!(
+                governance.update_proposal_status(proposal_id, true),
+                Err(Error::VotingPeriodNotEnded)
+            );
+
+            set_block(2 + VOTING_PERIOD);
+            assert_eq!(
+                governance.get_proposal_status(proposal_id),
+                Ok(ProposalStatus::Succeeded)
+            );
+            assert_eq!(governance.update_proposal_status(proposal_id, true), Ok(()));
+            assert_eq!(
+                governance.get_proposal(proposal_id).unwrap().status,
+                ProposalStatus::Executed
+            );
+            assert_eq!(
+                governance.update_proposal_status(proposal_id, true),
+                Err(Error::ProposalExecuted)
+            );
+        }
+
+        #[ink::test]
+        fn defeated_proposal_cannot_be_executed() {
+            let accounts = ink::env::test::default_accounts();
+            let (mut governance, proposal_id) = setup();
+            set_block(1);
+            assert_eq!(governance.vote_for(accounts.charlie, proposal_id, true), Ok(()));
+            assert_eq!(governance.vote_for(accounts.bob, proposal_id, false), Ok(()));
+
+            set_block(2 + VOTING_PERIOD);
+            assert_eq!(
+                governance.get_proposal_status(proposal_id),
+                Ok(ProposalStatus::Defeated)
+            );
+            assert_eq!(
+                governance.update_proposal_status(proposal_id, true),
+                Err(Error::ProposalRejected)
+            );
+            assert_eq!(
+                governance.vote_for(accounts.bob, proposal_id, true),
+                Err(Error::VotingPeriodEnded)
+            );
+        }
+
+        #[ink::test]
+        fn only_the_dao_marks_a_proposal_executed() {
+            let accounts = ink::env::test::default_accounts();
+            let (mut governance, proposal_id) = setup();
+            set_block(1);
+            assert_eq!(governance.vote_for(accounts.bob, proposal_id, true), Ok(()));
+
+            set_block(2 + VOTING_PERIOD);
+            set_caller(accounts.bob);
+            assert_eq!(
+                governance.update_proposal_status(proposal_id, true),
+                Err(Error::NotDao)
+            );
+            // Anyone can queue a succeeded proposal
+            assert_eq!(governance.update_proposal_status(proposal_id, false), Ok(()));
+            assert_eq!(
+                governance.get_proposal_status(proposal_id),
+                Ok(ProposalStatus::Queued)
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(governance.update_proposal_status(proposal_id, true), Ok(()));
+        }
+
+        #[ink::test]
+        fn cancelled_proposal_is_closed() {
+            let accounts = ink::env::test::default_accounts();
+            let (mut governance, proposal_id) = setup();
+            set_block(1);
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                governance.cancel_proposal(proposal_id),
+                Err(Error::NotProposalOwner)
+            );
+            // charlie proposed it
+            set_caller(accounts.charlie);
+            assert_eq!(governance.cancel_proposal(proposal_id), Ok(()));
+            set_caller(accounts.alice);
+            assert_eq!(
+                governance.vote_for(accounts.bob, proposal_id, true),
+                Err(Error::VotingPeriodEnded)
+            );
+            assert_eq!(
+                governance.cancel_proposal(proposal_id),
+                Err(Error::InvalidTransition)
+            );
+
+            set_block(2 + VOTING_PERIOD);
+            assert_eq!(
+                governance.update_proposal_status(proposal_id, true),
+                Err(Error::ProposalCancelled)
+            );
+        }
+
+        #[test]
+        fn approval_thresholds_work() {
+            let turnout = U256::from(100);
+            assert!(ApprovalThreshold::SimpleMajority.is_met(U256::from(51), turnout));
+            assert!(!ApprovalThreshold::SimpleMajority.is_met(U256::from(50), turnout));
+            assert!(ApprovalThreshold::SixtyPercent.is_met(U256::from(60), turnout));
+            assert!(!ApprovalThreshold::SixtyPercent.is_met(U256::from(59), turnout));
+            assert!(ApprovalThreshold::TwoThirds.is_met(U256::from(67), turnout));
+            assert!(!ApprovalThreshold::TwoThirds.is_met(U256::from(66), turnout));
+            assert!(!ApprovalThreshold::SimpleMajority.is_met(U256::zero(), U256::zero()));
+        }
+
+        #[ink::test]
+        fn total_voting_power_follows_voters() {
+            let accounts = ink::env::test::default_accounts();
+            let (mut governance, _) = setup();
+            assert_eq!(governance.get_total_voting_power(), U256::from(3));
+            assert_eq!(governance.update_voter(accounts.bob, U256::from(5)), Ok(()));
+            assert_eq!(governance.get_total_voting_power(), U256::from(6));
+            assert_eq!(governance.remove_voter(accounts.charlie), Ok(()));
+            assert_eq!(governance.get_total_voting_power(), U256::from(5));
+        }
+
+        #[ink::test]
+        fn proposal_below_quorum_is_rejected() {
+            let accounts = ink::env::test::default_accounts();
+            let (mut governance, _) = setup();
+            assert_eq!(governance.register_voter(accounts.django, U256::from(17)), Ok(()));
+            let proposal_id = governance.create_proposal(
+                b"Fund a workshop".to_vec(),
+                ProposalType::Spending,
+                Some(accounts.eve),
+                U256::from(100),
+                accounts.bob,
+            ).unwrap();
+
+            // 2 out of 20 is below the 20% quorum of Spending proposals
+            set_block(1);
+            assert_eq!(governance.vote_for(accounts.bob, proposal_id, true), Ok(()));
+            set_block(2 + VOTING_PERIOD);
+            assert_eq!(
+                governance.get_proposal_status(proposal_id),
+                Ok(ProposalStatus::Defeated)
+            );
+            assert_eq!(
+                governance.update_proposal_status(proposal_id, true),
+                Err(Error::QuorumNotReached)
+            );
+        }
+
+        #[ink::test]
+        fn voting_rules_are_set_per_proposal_type() {
+            let accounts = ink::env::test::default_accounts();
+            let (mut governance, spending_id) = setup();
+            let rules = VotingRules {
+                quorum_percent: 50,
+                approval: ApprovalThreshold::TwoThirds,
+            };
+            assert_eq!(
+                governance.set_voting_rules(
+                    ProposalType::NewMentor,
+                    VotingRules { quorum_percent: 101, approval: ApprovalThreshold::TwoThirds },
+                ),
+                Err(Error::InvalidVotingRules)
+            );
+            assert_eq!(governance.set_voting_rules(ProposalType::NewMentor, rules.clone()), Ok(()));
+            assert_eq!(governance.get_voting_rules(ProposalType::NewMentor), rules);
+            assert_eq!(
+                governance.get_voting_rules(ProposalType::Spending),
+                VotingRules::default_for(&ProposalType::Spending)
+            );
+            set_caller(accounts.bob);
+            assert_eq!(
+                governance.set_voting_rules(ProposalType::Spending, rules.clone()),
+                Err(Error::NotDao)
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(governance.update_voter(accounts.bob, U256::from(5)), Ok(()));
+            assert_eq!(governance.update_voter(accounts.charlie, U256::from(3)), Ok(()));
+            let mentor_id = governance.create_proposal(
+                b"New mentor".to_vec(),
+                ProposalType::NewMentor,
+                Some(accounts.eve),
+                U256::zero(),
+                accounts.bob,
+            ).unwrap();
+            // 5 yes out of 8 is enough for the Spending proposal (60%)
+            // but not for the mentor proposal (two thirds)
+            set_block(1);
+            for proposal_id in [spending_id, mentor_id] {
+                assert_eq!(governance.vote_for(accounts.bob, proposal_id, true), Ok(()));
+                assert_eq!(governance.vote_for(accounts.charlie, proposal_id, false), Ok(()));
+            }
+            set_block(2 + VOTING_PERIOD);
+            assert_eq!(
+                governance.get_proposal_status(spending_id),
+                Ok(ProposalStatus::Succeeded)
+            );
+            assert_eq!(
+                governance.get_proposal_status(mentor_id),
+                Ok(ProposalStatus::Defeated)
+            );
+        }
+
+        #[ink::test]
+        fn unexecuted_proposal_expires() {
+            let accounts = ink::env::test::default_accounts();
+            let (mut governance, proposal_id) = setup();
+            set_block(1);
+            assert_eq!(governance.vote_for(accounts.bob, proposal_id, true), Ok(()));
+
+            set_block(2 + VOTING_PERIOD + EXECUTION_PERIOD);
+            assert_eq!(
+                governance.get_proposal_status(proposal_id),
+                Ok(ProposalStatus::Expired)
+            );
+            assert_eq!(
+                governance.update_proposal_status(proposal_id, true),
+                Err(Error::ProposalExpired)
+            );
+        }
     }
     /*
 
@@ -1,6 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
 pub use self::my_erc20::{
+    Error,
     MyErc20,
     MyErc20Ref,
 };
@@ -8,10 +9,30 @@ pub use self::my_erc20::{
 #[ink::contract]
 mod my_erc20 {
     use ink::{
-        storage::Mapping,
+        storage::{
+            traits::ManualKey,
+            Lazy,
+            Mapping,
+        },
+        H256,
         U256,
     };
 
+    /// Version of the storage layout written by this code, see `migrate`.
+    pub const STORAGE_VERSION: u32 = 1;
+    /// Storage key of the version marker, kept out of the root struct so that it
+    /// can be read whatever the layout of the root.
+    pub const STORAGE_VERSION_KEY: u32 = 0x7665_7273;
+
+    /// Balance of an account from block `block` on, until its next checkpoint.
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Checkpoint {
+        pub block: BlockNumber,
+        pub balance: U256,
+    }
+
     /// A simple ERC-20 contract.
     #[ink(storage)]
     #[derive(Default)]
@@ -23,6 +44,15 @@ mod my_erc20 {
         /// Mapping of the token amount which an account is allowed to withdraw
         /// from another account.
         allowances: Mapping<(Address, Address), U256>,
+        /// Account allowed to upgrade the contract, the instantiating DAO by default.
+        admin: Address,
+        /// Version of the storage layout, bumped by `migrate` after an upgrade.
+        storage_version: Lazy<u32, ManualKey<STORAGE_VERSION_KEY>>,
+        /// History of the balance of each account, oldest first, at most one
+        /// checkpoint per block.
+        checkpoints: Mapping<(Address, u32), Checkpoint>,
+        /// Number of checkpoints of each account.
+        checkpoint_counts: Mapping<Address, u32>,
     }
 
     /// Event emitted when a token transfer occurs.
@@ -54,6 +84,10 @@ mod my_erc20 {
         InsufficientBalance,
         /// Returned if not enough allowance to fulfill a request is available.
         InsufficientAllowance,
+        /// Returned if the caller is not the admin of the contract.
+        NotAdmin,
+        /// Returned if the code hash could not be set.
+        UpgradeFailed,
     }
 
     /// The ERC-20 result type.
@@ -71,11 +105,18 @@ mod my_erc20 {
                 to: Some(caller),
                 value: total_supply,
             });
-            Self {
+            let mut my_erc20 = Self {
                 total_supply,
                 balances,
                 allowances: Default::default(),
-            }
+                admin: caller,
+                storage_version: Lazy::new(),
+                checkpoints: Default::default(),
+                checkpoint_counts: Default::default(),
+            };
+            my_erc20.storage_version.set(&STORAGE_VERSION);
+            my_erc20.write_checkpoint(&caller, total_supply);
+            my_erc20
         }
 
         /// Returns the total token supply.
@@ -105,6 +146,37 @@ mod my_erc20 {
             self.balances.get(owner).unwrap_or_default()
         }
 
+        /// Returns the balance `owner` held at the end of block `block`.
+        ///
+        /// Returns `0` if the account had no balance yet at that block.
+        #[ink(message)]
+        pub fn balance_of_at(&self, owner: Address, block: BlockNumber) -> U256 {
+            // Binary search of the last checkpoint written at or before `block`
+            let mut low = 0;
+            let mut high = self.checkpoint_counts.get(owner).unwrap_or_default();
+            while low < high {
+                let mid = low + (high - low) / 2;
+                let checkpoint_block = self
+                    .checkpoints
+                    .get((owner, mid))
+                    .map(|checkpoint| checkpoint.block)
+                    .unwrap_or_default();
+                if checkpoint_block <= block {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            match low.checked_sub(1) {
+                Some(index) => self
+                    .checkpoints
+                    .get((owner, index))
+                    .map(|checkpoint| checkpoint.balance)
+                    .unwrap_or_default(),
+                None => U256::zero(),
+            }
+        }
+
         /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
         ///
         /// Returns `0` if no allowance has been set.
@@ -188,6 +260,95 @@ mod my_erc20 {
             Ok(())
         }
 
+        /// Returns the account allowed to upgrade the contract.
+        #[ink(message)]
+        pub fn admin(&self) -> Address {
+            self.admin
+        }
+
+        /// Hands the admin role over to `admin`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotAdmin` error if the caller is not the current admin.
+        #[ink(message)]
+        pub fn set_admin(&mut self, admin: Address) -> Result<()> {
+            self.ensure_admin()?;
+            self.admin = admin;
+            Ok(())
+        }
+
+        /// Replaces the code of the contract with the uploaded code `code_hash`.
+        /// The new code is used from the next call on, which should be `migrate`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotAdmin` error if the caller is not the admin.
+        ///
+        /// Returns `UpgradeFailed` error if no code was uploaded for `code_hash`.
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: H256) -> Result<()> {
+            self.ensure_admin()?;
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::UpgradeFailed)
+        }
+
+        /// Returns the version of the storage layout.
+        #[ink(message)]
+        pub fn storage_version(&self) -> u32 {
+            self.storage_version.get().unwrap_or_default()
+        }
+
+        /// Migrates the storage written by an older code to `STORAGE_VERSION`.
+        /// Does nothing if the storage is already up to date.
+        ///
+        /// The root struct is decoded before any message runs, so an upgrade must
+        /// keep its layout: new state goes under its own key, in a `Mapping` or a
+        /// `Lazy` with a `ManualKey`, filled here from the old data.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotAdmin` error if the caller is not the admin.
+        #[ink(message)]
+        pub fn migrate(&mut self) -> Result<()> {
+            self.ensure_admin()?;
+            if self.storage_version() < STORAGE_VERSION {
+                self.storage_version.set(&STORAGE_VERSION);
+            }
+            Ok(())
+        }
+
+        /// Records `balance` as the balance of `owner` from the current block on.
+        /// A balance changed several times in a block keeps a single checkpoint.
+        fn write_checkpoint(&mut self, owner: &Address, balance: U256) {
+            let block = self.env().block_number();
+            let count = self.checkpoint_counts.get(owner).unwrap_or_default();
+            let last = count.checked_sub(1).and_then(|index| {
+                self.checkpoints
+                    .get((owner, index))
+                    .filter(|checkpoint| checkpoint.block == block)
+                    .map(|_| index)
+            });
+            let checkpoint = Checkpoint { block, balance };
+            match last {
+                Some(index) => {
+                    self.checkpoints.insert((owner, index), &checkpoint);
+                }
+                None => {
+                    self.checkpoints.insert((owner, count), &checkpoint);
+                    self.checkpoint_counts.insert(owner, &(count + 1));
+                }
+            }
+        }
+
+        fn ensure_admin(&self) -> Result<()> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAdmin)
+            }
+            Ok(())
+        }
+
         /// Transfers `value` amount of tokens from the caller's account to account `to`.
         ///
         /// On success a `Transfer` event is emitted.
@@ -208,10 +369,12 @@ mod my_erc20 {
             }
             // We checked that from_balance >= value
             #[allow(clippy::arithmetic_side_effects)]
-            self.balances.insert(from, &(from_balance - value));
-            let to_balance = self.balance_of_impl(to);
-            self.balances
-                .insert(to, &(to_balance.checked_add(value).unwrap()));
+            let from_balance = from_balance - value;
+            self.balances.insert(from, &from_balance);
+            self.write_checkpoint(from, from_balance);
+            let to_balance = self.balance_of_impl(to).checked_add(value).unwrap();
+            self.balances.insert(to, &to_balance);
+            self.write_checkpoint(to, to_balance);
             self.env().emit_event(Transfer {
                 from: Some(*from),
                 to: Some(*to),
@@ -498,6 +661,100 @@ mod my_erc20 {
             )
         }
 
+        #[ink::test]
+        fn balance_of_at_works() {
+            let accounts = ink::env::test::default_accounts();
+            set_caller(accounts.alice);
+            let mut my_erc20 = MyErc20::new(100.into());
+            let created_at = ink::env::block_number::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(my_erc20.transfer(accounts.bob, 10.into()), Ok(()));
+            // Several transfers in a block keep a single checkpoint.
+            assert_eq!(my_erc20.transfer(accounts.bob, 10.into()), Ok(()));
+            let first_transfer_at =
+                ink::env::block_number::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(my_erc20.transfer(accounts.bob, 30.into()), Ok(()));
+            let second_transfer_at =
+                ink::env::block_number::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(my_erc20.balance_of_at(accounts.alice, created_at), U256::from(100));
+            assert_eq!(my_erc20.balance_of_at(accounts.bob, created_at), U256::zero());
+            assert_eq!(
+                my_erc20.balance_of_at(accounts.alice, first_transfer_at),
+                U256::from(80)
+            );
+            assert_eq!(
+                my_erc20.balance_of_at(accounts.bob, first_transfer_at),
+                U256::from(20)
+            );
+            // Blocks without transfers keep the previous balance.
+            assert_eq!(
+                my_erc20.balance_of_at(accounts.bob, second_transfer_at - 1),
+                U256::from(20)
+            );
+            assert_eq!(
+                my_erc20.balance_of_at(accounts.bob, second_transfer_at),
+                U256::from(50)
+            );
+            assert_eq!(
+                my_erc20.balance_of_at(accounts.alice, second_transfer_at + 10),
+                my_erc20.balance_of(accounts.alice)
+            );
+        }
+
+        #[ink::test]
+        fn failed_transfer_does_not_write_checkpoint() {
+            let accounts = ink::env::test::default_accounts();
+            set_caller(accounts.alice);
+            let mut my_erc20 = MyErc20::new(100.into());
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            set_caller(accounts.bob);
+            assert_eq!(
+                my_erc20.transfer(accounts.eve, 10.into()),
+                Err(Error::InsufficientBalance)
+            );
+            let block = ink::env::block_number::<ink::env::DefaultEnvironment>();
+            assert_eq!(my_erc20.balance_of_at(accounts.bob, block), U256::zero());
+            assert_eq!(my_erc20.balance_of_at(accounts.eve, block), U256::zero());
+            assert_eq!(my_erc20.balance_of_at(accounts.alice, block), U256::from(100));
+        }
+
+        #[ink::test]
+        fn only_admin_can_set_admin() {
+            let accounts = ink::env::test::default_accounts();
+            set_caller(accounts.alice);
+            let mut my_erc20 = MyErc20::new(100.into());
+            assert_eq!(my_erc20.admin(), accounts.alice);
+
+            // Bob is not the admin.
+            set_caller(accounts.bob);
+            assert_eq!(my_erc20.set_admin(accounts.bob), Err(Error::NotAdmin));
+            assert_eq!(my_erc20.set_code(H256::zero()), Err(Error::NotAdmin));
+
+            // Alice hands the admin role over to Bob.
+            set_caller(accounts.alice);
+            assert_eq!(my_erc20.set_admin(accounts.bob), Ok(()));
+            assert_eq!(my_erc20.admin(), accounts.bob);
+            assert_eq!(my_erc20.migrate(), Err(Error::NotAdmin));
+        }
+
+        #[ink::test]
+        fn migrate_is_a_no_op_when_up_to_date() {
+            let accounts = ink::env::test::default_accounts();
+            set_caller(accounts.alice);
+            let mut my_erc20 = MyErc20::new(100.into());
+            assert_eq!(my_erc20.storage_version(), STORAGE_VERSION);
+
+            assert_eq!(my_erc20.migrate(), Ok(()));
+            assert_eq!(my_erc20.storage_version(), STORAGE_VERSION);
+            assert_eq!(my_erc20.balance_of(accounts.alice), U256::from(100));
+        }
+
         fn encoded_into_hash<T>(entity: T) -> Hash
         where
             T: ink::scale::Encode,